@@ -0,0 +1,64 @@
+/*
+Generate the module declarations and registration code for every solution.
+
+Each `src/yYYYY.rs` becomes a year module, and each `src/yYYYY/day_N.rs` in it
+is declared as `day_N` and registered as `day_N::DayN`. Adding a day is then
+just a matter of adding its file; nothing has to be edited by hand.
+*/
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Numbers parsed from names like `y2022.rs` or `day_15.rs`
+fn numbered(dir: &Path, prefix: &str, suffix: &str) -> Vec<(i32, PathBuf)> {
+    let mut found: Vec<(i32, PathBuf)> = fs::read_dir(dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let name = path.file_name()?.to_str()?;
+            let number = name
+                .strip_prefix(prefix)?
+                .strip_suffix(suffix)?
+                .parse()
+                .ok()?;
+            Some((number, path))
+        })
+        .collect();
+    found.sort();
+    found
+}
+
+fn main() {
+    let src = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src");
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    println!("cargo:rerun-if-changed=src");
+
+    let mut years = String::new();
+    let mut register_years = String::new();
+    for (year, year_path) in numbered(&src, "y", ".rs") {
+        years.push_str(&format!("#[path = {:?}]\npub mod y{year};\n", year_path));
+        register_years.push_str(&format!("    y{year}::register(registry);\n"));
+
+        let mut days = String::new();
+        let mut register_days = String::new();
+        let year_dir = src.join(format!("y{year}"));
+        if year_dir.is_dir() {
+            for (day, day_path) in numbered(&year_dir, "day_", ".rs") {
+                days.push_str(&format!("#[path = {:?}]\npub mod day_{day};\n", day_path));
+                register_days.push_str(&format!(
+                    "    registry.register(YEAR, {day}, day_{day}::Day{day});\n"
+                ));
+            }
+        }
+        fs::write(
+            out_dir.join(format!("y{year}.rs")),
+            format!("{days}\npub fn register(registry: &mut Registry) {{\n{register_days}}}\n"),
+        )
+        .unwrap();
+    }
+    fs::write(
+        out_dir.join("years.rs"),
+        format!("{years}\nfn register_years(registry: &mut Registry) {{\n{register_years}}}\n"),
+    )
+    .unwrap();
+}
@@ -0,0 +1,330 @@
+/*
+Known answers for each year, stored in `answers/{year}.toml`.
+
+Each entry records the verified answer for a day & part once it's known,
+along with any guesses that were rejected and the hint that came back with them,
+and the answer to the puzzle's example if it's been fetched:
+
+    [[answer]]
+    day = 13
+    part = 1
+
+    [[answer.rejected]]
+    answer = "4781"
+    hint = "too low"
+*/
+use crate::solver::{Answer, Part};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const HEADER: &str = "\
+# Verified answers and rejected guesses for each day & part.
+# `correct` is filled in once an answer has been accepted; `rejected` keeps
+# every wrong guess along with the hint that came back with it. `example` is
+# the answer for the example in the puzzle description.
+
+";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Hint {
+    #[serde(rename = "too high")]
+    TooHigh,
+    #[serde(rename = "too low")]
+    TooLow,
+    #[default]
+    #[serde(rename = "incorrect")]
+    Incorrect,
+}
+
+impl fmt::Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Hint::TooHigh => write!(f, "too high"),
+            Hint::TooLow => write!(f, "too low"),
+            Hint::Incorrect => write!(f, "incorrect"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rejected {
+    pub answer: String,
+    #[serde(default)]
+    pub hint: Hint,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnswerRecord {
+    pub day: i32,
+    pub part: Part,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correct: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rejected: Vec<Rejected>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub example: Option<String>,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct AnswerBook {
+    #[serde(default, rename = "answer")]
+    pub answers: Vec<AnswerRecord>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail(String),
+    Unknown(String),
+}
+
+impl Verdict {
+    pub fn is_fail(&self) -> bool {
+        matches!(self, Verdict::Fail(_))
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "PASS"),
+            Verdict::Fail(why) => write!(f, "FAIL ({why})"),
+            Verdict::Unknown(why) => write!(f, "UNKNOWN ({why})"),
+        }
+    }
+}
+
+impl AnswerBook {
+    pub fn path(year: i32) -> PathBuf {
+        PathBuf::from(format!("answers/{year}.toml"))
+    }
+
+    pub fn load(year: i32) -> Result<AnswerBook, String> {
+        AnswerBook::load_from(&AnswerBook::path(year))
+    }
+
+    pub fn load_from(path: &Path) -> Result<AnswerBook, String> {
+        // A year without an answer file just doesn't know any answers yet
+        match fs::read_to_string(path) {
+            Ok(contents) => AnswerBook::parse(&contents)
+                .map_err(|e| format!("Could not parse {}: {e}", path.display())),
+            Err(_) => Ok(AnswerBook::default()),
+        }
+    }
+
+    pub fn parse(contents: &str) -> Result<AnswerBook, toml::de::Error> {
+        toml::from_str(contents)
+    }
+
+    pub fn save(&self, year: i32) -> Result<(), String> {
+        self.save_to(&AnswerBook::path(year))
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<(), String> {
+        let contents = toml::to_string(self).map_err(|e| e.to_string())?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("Could not create {}: {e}", dir.display()))?;
+        }
+        fs::write(path, format!("{HEADER}{contents}"))
+            .map_err(|e| format!("Could not write {}: {e}", path.display()))
+    }
+
+    pub fn get(&self, day: i32, part: Part) -> Option<&AnswerRecord> {
+        self.answers
+            .iter()
+            .find(|record| record.day == day && record.part == part)
+    }
+
+    pub fn get_mut(&mut self, day: i32, part: Part) -> &mut AnswerRecord {
+        // add an empty record if there isn't one, in order by day & part
+        let existing = self
+            .answers
+            .iter()
+            .position(|r| (r.day, r.part) == (day, part));
+        let index = match existing {
+            Some(index) => index,
+            None => {
+                let index = self
+                    .answers
+                    .iter()
+                    .position(|r| (r.day, r.part) > (day, part))
+                    .unwrap_or(self.answers.len());
+                let record = AnswerRecord {
+                    day,
+                    part,
+                    correct: None,
+                    rejected: Vec::new(),
+                    example: None,
+                };
+                self.answers.insert(index, record);
+                index
+            }
+        };
+        &mut self.answers[index]
+    }
+
+    pub fn check_example(&self, day: i32, part: Part, answer: &Answer) -> Verdict {
+        match self.get(day, part).and_then(|r| r.example.as_ref()) {
+            Some(example) if *example == answer.to_string() => Verdict::Pass,
+            Some(example) => Verdict::Fail(format!("expected {example}")),
+            None => Verdict::Unknown("no example answer".to_string()),
+        }
+    }
+
+    pub fn check(&self, day: i32, part: Part, answer: &Answer) -> Verdict {
+        let record = self.get(day, part);
+        let answer_text = answer.to_string();
+        if let Some(correct) = record.and_then(|r| r.correct.as_ref()) {
+            return match *correct == answer_text {
+                true => Verdict::Pass,
+                false => Verdict::Fail(format!("expected {correct}")),
+            };
+        }
+        if *answer == Answer::Unsolved {
+            return Verdict::Unknown("unsolved".to_string());
+        }
+        let rejected = record.map(|r| r.rejected.as_slice()).unwrap_or_default();
+        if let Some(guess) = rejected.iter().find(|g| g.answer == answer_text) {
+            return Verdict::Fail(format!("already rejected as {}", guess.hint));
+        }
+        // a number on the wrong side of a too high / too low guess can't be right either
+        if let Answer::Number(n) = answer {
+            for guess in rejected {
+                let Ok(guess_n) = guess.answer.parse::<i64>() else {
+                    continue;
+                };
+                match guess.hint {
+                    Hint::TooHigh if *n > guess_n => {
+                        return Verdict::Fail(format!("higher than {guess_n}, which was too high"))
+                    }
+                    Hint::TooLow if *n < guess_n => {
+                        return Verdict::Fail(format!("lower than {guess_n}, which was too low"))
+                    }
+                    _ => (),
+                }
+            }
+        }
+        Verdict::Unknown("new answer".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    fn mock_book() -> AnswerBook {
+        AnswerBook::parse(indoc! {r#"
+            [[answer]]
+            day = 1
+            part = 1
+            correct = "24000"
+
+            [[answer]]
+            day = 13
+            part = 1
+            rejected = [
+                { answer = "4781", hint = "too low" },
+                { answer = "5629", hint = "too high" },
+                { answer = "5000" },
+            ]
+        "#})
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let book = mock_book();
+        assert_eq!(book.answers.len(), 2);
+        let record = book.get(13, Part::One).unwrap();
+        assert_eq!(record.correct, None);
+        assert_eq!(record.rejected[1].hint, Hint::TooHigh);
+        assert_eq!(record.rejected[2].hint, Hint::Incorrect);
+        assert!(book.get(13, Part::Two).is_none());
+        assert!(AnswerBook::parse("[[answer]]\nday = 1\npart = 3\n").is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let book = mock_book();
+        assert_eq!(
+            book.check(1, Part::One, &Answer::Number(24000)),
+            Verdict::Pass
+        );
+        assert_eq!(
+            book.check(1, Part::One, &Answer::Number(24001)),
+            Verdict::Fail("expected 24000".to_string())
+        );
+        assert_eq!(
+            book.check(13, Part::One, &Answer::Number(4781)),
+            Verdict::Fail("already rejected as too low".to_string())
+        );
+        assert_eq!(
+            book.check(13, Part::One, &Answer::Number(6000)),
+            Verdict::Fail("higher than 5629, which was too high".to_string())
+        );
+        assert_eq!(
+            book.check(13, Part::One, &Answer::Number(4000)),
+            Verdict::Fail("lower than 4781, which was too low".to_string())
+        );
+        assert!(book.check(13, Part::One, &Answer::Number(5000)).is_fail());
+        assert_eq!(
+            book.check(13, Part::One, &Answer::Number(5001)),
+            Verdict::Unknown("new answer".to_string())
+        );
+        assert_eq!(
+            book.check(2, Part::Two, &Answer::Unsolved),
+            Verdict::Unknown("unsolved".to_string())
+        );
+    }
+
+    #[test]
+    fn test_check_example() {
+        let mut book = mock_book();
+        book.get_mut(1, Part::One).example = Some("7".to_string());
+        assert_eq!(
+            book.check_example(1, Part::One, &Answer::Number(7)),
+            Verdict::Pass
+        );
+        assert!(book
+            .check_example(1, Part::One, &Answer::Number(8))
+            .is_fail());
+        assert_eq!(
+            book.check_example(1, Part::Two, &Answer::Number(7)),
+            Verdict::Unknown("no example answer".to_string())
+        );
+    }
+
+    #[test]
+    fn test_save() {
+        let mut book = mock_book();
+        book.get_mut(2, Part::Two).example = Some("12".to_string());
+        book.get_mut(2, Part::One).correct = Some("15".to_string());
+        let days: Vec<(i32, Part)> = book.answers.iter().map(|r| (r.day, r.part)).collect();
+        assert_eq!(
+            days,
+            vec![
+                (1, Part::One),
+                (2, Part::One),
+                (2, Part::Two),
+                (13, Part::One)
+            ]
+        );
+
+        let path = crate::fetch::tests::temp_dir("answers").join("2022.toml");
+        book.save_to(&path).unwrap();
+        assert_eq!(AnswerBook::load_from(&path).unwrap(), book);
+        assert!(fs::read_to_string(&path).unwrap().starts_with(HEADER));
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_answer_file() {
+        // the checked-in answers for 2022 should always parse
+        let book = AnswerBook::load(2022).unwrap();
+        assert!(book.get(13, Part::One).is_some());
+    }
+}
@@ -1,92 +1,319 @@
-#[derive(PartialEq, Debug)]
-pub struct Grid {
+use std::fmt;
+
+pub mod animate;
+pub mod cpu;
+pub mod geom;
+pub mod ocr;
+pub mod render;
+pub mod search;
+pub mod sparse;
+
+pub use geom::Direction;
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Grid<T = u8> {
     pub num_rows: usize,
     pub num_cols: usize,
-    pub data: Vec<u8>,
+    pub data: Vec<T>,
 }
 
-#[derive(Debug, PartialEq)]
-pub enum Error {
-    IndexError,
+#[derive(Debug, PartialEq, Clone)]
+pub enum GridError {
+    OutOfBounds {
+        row: usize,
+        col: usize,
+        num_rows: usize,
+        num_cols: usize,
+    },
+    BadIndex {
+        index: usize,
+        len: usize,
+    },
+    RaggedRow {
+        row: usize,
+        len: usize,
+        expected: usize,
+    },
 }
 
-impl Grid {
-    pub fn get(&self, row: usize, col: usize) -> Result<u8, Error> {
-        // Get the value at a given `row` and `col` in the grid.
-        if row > self.num_rows || col > self.num_cols {
-            return Err(Error::IndexError);
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GridError::OutOfBounds {
+                row,
+                col,
+                num_rows,
+                num_cols,
+            } => write!(
+                f,
+                "({row}, {col}) is outside of the {num_rows} x {num_cols} grid"
+            ),
+            GridError::BadIndex { index, len } => {
+                write!(f, "index {index} is outside of a grid with {len} cells")
+            }
+            GridError::RaggedRow { row, len, expected } => write!(
+                f,
+                "row {row} has {len} columns, but the rows before it have {expected}"
+            ),
         }
-        let value: &u8 = &self.data[row * self.num_cols + col];
-        Ok(*value)
     }
+}
 
-    pub fn disp(&self) {
-        for row in 0..self.num_rows {
-            for col in 0..self.num_cols {
-                print!("{}", self.data[row * self.num_cols + col] as char);
+impl std::error::Error for GridError {}
+
+// Iterator over the neighbors of one cell, as (index, direction) pairs.
+// Off the edge cells are skipped, unless `wrapping()` is used to wrap
+// around to the other side of the grid.
+pub struct Neighbors<'a, T> {
+    grid: &'a Grid<T>,
+    row: usize,
+    col: usize,
+    directions: std::slice::Iter<'static, Direction>,
+    wrap: bool,
+}
+
+impl<T> Neighbors<'_, T> {
+    pub fn wrapping(mut self) -> Self {
+        self.wrap = true;
+        self
+    }
+}
+
+impl<T> Iterator for Neighbors<'_, T> {
+    type Item = (usize, Direction);
+
+    fn next(&mut self) -> Option<(usize, Direction)> {
+        let (num_rows, num_cols) = (self.grid.num_rows as isize, self.grid.num_cols as isize);
+        for dir in self.directions.by_ref() {
+            let (dr, dc) = dir.offset();
+            let (mut row, mut col) = (self.row as isize + dr, self.col as isize + dc);
+            if self.wrap {
+                row = row.rem_euclid(num_rows);
+                col = col.rem_euclid(num_cols);
+            } else if row < 0 || col < 0 || row >= num_rows || col >= num_cols {
+                continue;
             }
-            println!();
+            return Some((row as usize * self.grid.num_cols + col as usize, *dir));
         }
+        None
     }
+}
 
-    pub fn loc(&self, index: usize) -> Result<(usize, usize), Error> {
-        // Get the row and column of a given index
-        if index > self.data.len() {
-            return Err(Error::IndexError);
+// Iterator over the cells in a straight line from one cell (not including it)
+// to the edge of the grid, as indexes.
+pub struct Ray<'a, T> {
+    grid: &'a Grid<T>,
+    row: isize,
+    col: isize,
+    direction: Direction,
+}
+
+impl<'a, T> Ray<'a, T> {
+    pub fn take_until_blocked<F: Fn(&T) -> bool>(self, blocked: F) -> TakeUntilBlocked<'a, T, F> {
+        // Like `take_while`, but the blocking cell is included: it can be seen,
+        // but nothing past it can.
+        TakeUntilBlocked {
+            ray: self,
+            blocked,
+            done: false,
         }
-        let row: usize = index / self.num_cols;
-        let col: usize = index % self.num_cols;
-        Ok((row, col))
     }
+}
 
-    pub fn ind(&self, row: usize, col: usize) -> Result<usize, Error> {
-        // Get the index at a given `row` and `col` in the grid
-        if row > self.num_rows || col > self.num_cols {
-            return Err(Error::IndexError);
+impl<T> Iterator for Ray<'_, T> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let (dr, dc) = self.direction.offset();
+        let (row, col) = (self.row + dr, self.col + dc);
+        if row < 0
+            || col < 0
+            || row >= self.grid.num_rows as isize
+            || col >= self.grid.num_cols as isize
+        {
+            return None;
         }
-        Ok(row * self.num_cols + col)
+        (self.row, self.col) = (row, col);
+        Some(row as usize * self.grid.num_cols + col as usize)
     }
+}
 
-    pub fn neighbors_lateral(&self, index: usize) -> Result<Vec<usize>, Error> {
-        match self.loc(index) {
-            Ok((row, col)) => {
-                let mut neighbors: Vec<usize> = Vec::new();
-                if col > 0 {
-                    neighbors.push(index - 1);
-                }
-                if col < self.num_cols - 1 {
-                    neighbors.push(index + 1);
-                }
-                if row > 0 {
-                    neighbors.push(self.ind(row - 1, col).unwrap());
-                }
-                if row < self.num_rows - 1 {
-                    neighbors.push(self.ind(row + 1, col).unwrap());
-                }
-                // behind, in front, above below
-                Ok(neighbors)
-            }
-            Err(e) => Err(e),
+pub struct TakeUntilBlocked<'a, T, F> {
+    ray: Ray<'a, T>,
+    blocked: F,
+    done: bool,
+}
+
+impl<T, F: Fn(&T) -> bool> Iterator for TakeUntilBlocked<'_, T, F> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.done {
+            return None;
         }
+        let index = self.ray.next()?;
+        self.done = (self.blocked)(&self.ray.grid.data[index]);
+        Some(index)
     }
+}
 
-    pub fn build(contents: String) -> Grid {
-        let mut data: Vec<u8> = Vec::new();
+impl<T> Grid<T> {
+    pub fn new(num_rows: usize, num_cols: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            num_rows,
+            num_cols,
+            data: vec![fill; num_rows * num_cols],
+        }
+    }
+
+    pub fn parse(contents: &str, cell: impl Fn(char) -> T) -> Result<Grid<T>, GridError> {
+        // Build a grid from lines of text, turning each character into a cell
+        let mut data: Vec<T> = Vec::new();
         let mut num_rows: usize = 0;
         let mut num_cols: usize = 0;
         for line in contents.lines() {
-            let mut l: Vec<u8> = line.chars().map(|c| c as u8).collect();
-            if num_cols != 0 && line.len() != num_cols {
-                panic!("Unequal number of columns in input!")
+            let len = line.chars().count();
+            if num_rows != 0 && len != num_cols {
+                return Err(GridError::RaggedRow {
+                    row: num_rows,
+                    len,
+                    expected: num_cols,
+                });
             }
-            num_cols = line.len();
-            data.append(&mut l);
+            num_cols = len;
+            data.extend(line.chars().map(&cell));
             num_rows += 1;
         }
-        Grid {
+        Ok(Grid {
             num_rows,
             num_cols,
             data,
+        })
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, value: T) -> Result<(), GridError> {
+        let index = self.ind(row, col)?;
+        self.data[index] = value;
+        Ok(())
+    }
+
+    pub fn checked_get(&self, row: usize, col: usize) -> Option<&T> {
+        // Like `get`, but for when being off the grid isn't an error
+        self.ind(row, col).ok().map(|index| &self.data[index])
+    }
+
+    pub fn loc(&self, index: usize) -> Result<(usize, usize), GridError> {
+        // Get the row and column of a given index
+        if index >= self.data.len() {
+            return Err(GridError::BadIndex {
+                index,
+                len: self.data.len(),
+            });
+        }
+        let row: usize = index / self.num_cols;
+        let col: usize = index % self.num_cols;
+        Ok((row, col))
+    }
+
+    pub fn ind(&self, row: usize, col: usize) -> Result<usize, GridError> {
+        // Get the index at a given `row` and `col` in the grid
+        if row >= self.num_rows || col >= self.num_cols {
+            return Err(GridError::OutOfBounds {
+                row,
+                col,
+                num_rows: self.num_rows,
+                num_cols: self.num_cols,
+            });
+        }
+        Ok(row * self.num_cols + col)
+    }
+
+    pub fn neighbors_lateral(&self, index: usize) -> Result<Vec<usize>, GridError> {
+        // behind, in front, above below
+        Ok(self.neighbors4(index)?.map(|(n, _)| n).collect())
+    }
+
+    pub fn neighbors(
+        &self,
+        index: usize,
+        directions: &'static [Direction],
+    ) -> Result<Neighbors<'_, T>, GridError> {
+        let (row, col) = self.loc(index)?;
+        Ok(Neighbors {
+            grid: self,
+            row,
+            col,
+            directions: directions.iter(),
+            wrap: false,
+        })
+    }
+
+    pub fn ray(&self, index: usize, direction: Direction) -> Result<Ray<'_, T>, GridError> {
+        let (row, col) = self.loc(index)?;
+        Ok(Ray {
+            grid: self,
+            row: row as isize,
+            col: col as isize,
+            direction,
+        })
+    }
+
+    pub fn neighbors4(&self, index: usize) -> Result<Neighbors<'_, T>, GridError> {
+        self.neighbors(index, &Direction::LATERAL)
+    }
+
+    pub fn neighbors_diagonal(&self, index: usize) -> Result<Neighbors<'_, T>, GridError> {
+        self.neighbors(index, &Direction::DIAGONAL)
+    }
+
+    pub fn neighbors8(&self, index: usize) -> Result<Neighbors<'_, T>, GridError> {
+        self.neighbors(index, &Direction::ALL)
+    }
+}
+
+// `grid[(row, col)]`, for when being off the grid is a bug: it panics
+impl<T> std::ops::Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        match self.ind(row, col) {
+            Ok(index) => &self.data[index],
+            Err(e) => panic!("{e}"),
+        }
+    }
+}
+
+impl<T> std::ops::IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        match self.ind(row, col) {
+            Ok(index) => &mut self.data[index],
+            Err(e) => panic!("{e}"),
+        }
+    }
+}
+
+impl<T: Copy> Grid<T> {
+    pub fn get(&self, row: usize, col: usize) -> Result<T, GridError> {
+        // Get the value at a given `row` and `col` in the grid.
+        let index = self.ind(row, col)?;
+        Ok(self.data[index])
+    }
+}
+
+impl Grid<u8> {
+    pub fn build(contents: String) -> Result<Grid, GridError> {
+        Grid::parse(&contents, |c| c as u8)
+    }
+
+    pub fn disp(&self) {
+        for row in 0..self.num_rows {
+            for col in 0..self.num_cols {
+                print!("{}", self.data[row * self.num_cols + col] as char);
+            }
+            println!();
         }
     }
 }
@@ -129,7 +356,7 @@ pub mod tests {
         8894
         "
         );
-        assert_eq!(b2(g1.clone()), Grid::build(g1.to_string()));
+        assert_eq!(b2(g1.clone()), Grid::build(g1.to_string()).unwrap());
     }
     fn mock_grid_2() -> Grid {
         let g2 = indoc!(
@@ -141,7 +368,7 @@ pub mod tests {
         "
         )
         .to_string();
-        Grid::build(g2)
+        Grid::build(g2).unwrap()
     }
     pub fn mock_grid() -> Grid {
         let digits: Vec<u8> = "1234567890".chars().map(|c| c as u8).collect();
@@ -177,22 +404,102 @@ pub mod tests {
         assert_eq!(neighbor_letters(&g2, 2, 4), vec!['n', 'j', 't']);
     }
 
+    #[test]
+    fn test_neighbor_iterators() {
+        let g2 = mock_grid_2();
+        let letters = |neighbors: Neighbors<u8>| -> String {
+            neighbors.map(|(n, _)| g2.data[n] as char).collect()
+        };
+        let m = g2.ind(2, 2).unwrap();
+        assert_eq!(letters(g2.neighbors4(m).unwrap()), "lnhr");
+        assert_eq!(letters(g2.neighbors_diagonal(m).unwrap()), "giqs");
+        assert_eq!(letters(g2.neighbors8(m).unwrap()), "lnhrgiqs");
+        assert_eq!(letters(g2.neighbors8(0).unwrap()), "bfg");
+        assert_eq!(letters(g2.neighbors_diagonal(4).unwrap()), "i");
+        assert!(g2.neighbors8(20).is_err());
+
+        let mut neighbors = g2.neighbors4(0).unwrap();
+        assert_eq!(neighbors.next(), Some((1, Direction::Right)));
+        assert_eq!(neighbors.next(), Some((5, Direction::Down)));
+        assert_eq!(neighbors.next(), None);
+
+        // wrapping around the edges, the corner sees the other three corners
+        assert_eq!(letters(g2.neighbors4(0).unwrap().wrapping()), "ebpf");
+        assert_eq!(
+            letters(g2.neighbors_diagonal(0).unwrap().wrapping()),
+            "tqjg"
+        );
+        assert_eq!(g2.neighbors8(19).unwrap().wrapping().count(), 8);
+    }
+
+    #[test]
+    fn test_ray() {
+        let g2 = mock_grid_2();
+        let letters = |ray: &mut dyn Iterator<Item = usize>| -> String {
+            ray.map(|n| g2.data[n] as char).collect()
+        };
+        let m = g2.ind(2, 2).unwrap();
+        assert_eq!(letters(&mut g2.ray(m, Direction::Left).unwrap()), "lk");
+        assert_eq!(letters(&mut g2.ray(m, Direction::Up).unwrap()), "hc");
+        assert_eq!(letters(&mut g2.ray(m, Direction::Down).unwrap()), "r");
+        assert_eq!(letters(&mut g2.ray(m, Direction::UpRight).unwrap()), "ie");
+        assert_eq!(letters(&mut g2.ray(m, Direction::DownLeft).unwrap()), "q");
+        assert_eq!(g2.ray(4, Direction::Right).unwrap().count(), 0);
+        assert!(g2.ray(20, Direction::Right).is_err());
+
+        // the cell that blocks the view is still part of it
+        let mut ray = g2
+            .ray(0, Direction::Right)
+            .unwrap()
+            .take_until_blocked(|c| *c == b'c');
+        assert_eq!(letters(&mut ray), "bc");
+        let mut ray = g2
+            .ray(0, Direction::Right)
+            .unwrap()
+            .take_until_blocked(|_| false);
+        assert_eq!(letters(&mut ray), "bcde");
+    }
+
     #[test]
     fn test_loc() {
         let grid = mock_grid();
         assert_eq!(grid.loc(2), Ok((0, 2)));
         assert_eq!(grid.loc(8), Ok((1, 3)));
-        assert_eq!(grid.loc(8888), Err(Error::IndexError));
+        assert_eq!(
+            grid.loc(8888),
+            Err(GridError::BadIndex {
+                index: 8888,
+                len: 10
+            })
+        );
+        // one past the end used to slip through
+        assert!(grid.loc(10).is_err());
     }
     #[test]
     fn test_get() {
-        let grid = mock_grid();
+        let mut grid = mock_grid();
         assert_eq!(grid.get(0, 0), Ok(49));
         assert_eq!(grid.get(0, 4), Ok(53));
         assert_eq!(grid.get(1, 2), Ok(56));
         assert_eq!(grid.get(1, 4), Ok(48));
-        assert_eq!(grid.get(1, 10), Err(Error::IndexError));
-        assert_eq!(grid.get(10, 1), Err(Error::IndexError));
+        assert_eq!(
+            grid.get(1, 10),
+            Err(GridError::OutOfBounds {
+                row: 1,
+                col: 10,
+                num_rows: 2,
+                num_cols: 5
+            })
+        );
+        assert!(grid.get(10, 1).is_err());
+        assert!(grid.get(2, 0).is_err());
+        assert!(grid.get(0, 5).is_err());
+        assert!(grid.ind(1, 5).is_err());
+        assert_eq!(grid.checked_get(1, 4), Some(&48));
+        assert_eq!(grid.checked_get(1, 5), None);
+        assert_eq!(grid[(1, 0)], 54);
+        grid[(1, 0)] = 0;
+        assert_eq!(grid.get(1, 0), Ok(0));
 
         let g2 = mock_grid_2();
         assert_eq!(g2.get(0, 0), Ok('a' as u8));
@@ -201,12 +508,45 @@ pub mod tests {
         assert_eq!(g2.get(1, 0), Ok('f' as u8));
     }
 
+    #[test]
+    #[should_panic(expected = "(0, 5) is outside of the 2 x 5 grid")]
+    fn test_index_off_the_edge() {
+        // no wrapping onto the next row
+        let _ = mock_grid()[(0, 5)];
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Cell {
+        Open,
+        Wall,
+    }
+
+    #[test]
+    fn test_parse() {
+        let wall = |c| match c {
+            '#' => Cell::Wall,
+            _ => Cell::Open,
+        };
+        let grid = Grid::parse("#.#\n..#\n", wall).unwrap();
+        assert_eq!((grid.num_rows, grid.num_cols), (2, 3));
+        assert_eq!(grid.get(0, 1), Ok(Cell::Open));
+        assert_eq!(grid.get(1, 2), Ok(Cell::Wall));
+        assert_eq!(grid.neighbors_lateral(4), Ok(vec![3, 5, 1]));
+
+        let mut grid = Grid::new(2, 2, Cell::Open);
+        grid.set(1, 0, Cell::Wall).unwrap();
+        assert_eq!(
+            grid.data,
+            vec![Cell::Open, Cell::Open, Cell::Wall, Cell::Open]
+        );
+    }
+
     #[test]
     fn test_grid() {
         // TODO: Fix failing test
         let test_input = String::from("12345\n67890\n");
         let test_grid = mock_grid();
-        assert_eq!(Grid::build(test_input), test_grid);
+        assert_eq!(Grid::build(test_input), Ok(test_grid));
         let test_grid_2 = mock_grid_2();
         assert_eq!(test_grid_2.num_cols, 5);
         assert_eq!(test_grid_2.num_rows, 4);
@@ -0,0 +1,315 @@
+/*
+Record a simulation one step at a time, and play it back afterwards.
+
+A `Recorder` keeps a frame from each step: anything at all, turned into an
+`Image` only when it's saved. So that a long simulation doesn't fill up the
+memory, once there are more than `limit` frames every other one is thrown away,
+and from then on only every other step is kept.
+
+`save` writes the frames out if they've been asked for:
+- with `AOC_RENDER=<dir>` as `<dir>/<name>.gif`, and one PPM per frame in
+  `<dir>/<name>/`
+- with `AOC_REPLAY=<frames per second>` as an animation in the terminal
+
+Frames are blown up one at a time as they're written, and never to more than
+`MAX_SIZE` pixels across, so a big simulation doesn't turn into gigabytes.
+*/
+use super::render::{self, Image, Rgb};
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+pub const REPLAY_VAR: &str = "AOC_REPLAY";
+pub const DEFAULT_LIMIT: usize = 200;
+pub const MAX_SIZE: usize = 800;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recorder<F> {
+    frames: Vec<F>,
+    enabled: bool,
+    limit: usize,
+    // keep one step out of every `every`
+    every: usize,
+    steps: usize,
+}
+
+impl<F> Recorder<F> {
+    pub fn new() -> Recorder<F> {
+        Recorder {
+            frames: Vec::new(),
+            enabled: true,
+            limit: DEFAULT_LIMIT,
+            every: 1,
+            steps: 0,
+        }
+    }
+
+    // a recorder that ignores everything, so simulations can always record
+    pub fn disabled() -> Recorder<F> {
+        Recorder {
+            enabled: false,
+            ..Recorder::new()
+        }
+    }
+
+    // only record if the frames will be saved or replayed
+    pub fn from_env() -> Recorder<F> {
+        match render::render_dir().is_some() || replay_fps().is_some() {
+            true => Recorder::new(),
+            false => Recorder::disabled(),
+        }
+    }
+
+    pub fn limit(mut self, limit: usize) -> Recorder<F> {
+        self.limit = limit.max(1);
+        self
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    // Call after every step. `frame` is only called for the steps being kept.
+    pub fn record(&mut self, frame: impl FnOnce() -> F) {
+        if !self.enabled {
+            return;
+        }
+        if self.steps.is_multiple_of(self.every) {
+            self.frames.push(frame());
+            if self.frames.len() > self.limit {
+                let mut index = 0;
+                self.frames.retain(|_| {
+                    index += 1;
+                    index % 2 == 1
+                });
+                self.every *= 2;
+            }
+        }
+        self.steps += 1;
+    }
+
+    // Always keep this one, e.g. the final state, whichever step it's on.
+    // Like `record`, `frame` isn't called if nothing's being recorded.
+    pub fn push(&mut self, frame: impl FnOnce() -> F) {
+        if self.enabled {
+            self.frames.push(frame());
+        }
+    }
+
+    pub fn frames(&self) -> &[F] {
+        &self.frames
+    }
+}
+
+impl<F> Default for Recorder<F> {
+    fn default() -> Self {
+        Recorder::new()
+    }
+}
+
+pub fn replay_fps() -> Option<f64> {
+    env::var(REPLAY_VAR)
+        .ok()
+        .and_then(|fps| fps.parse::<f64>().ok())
+        .filter(|fps| *fps > 0.0)
+}
+
+// Frames should all be the same size; any that aren't are drawn in the top
+// left corner of a canvas big enough for the largest.
+fn canvas_size(images: &[Image]) -> (usize, usize) {
+    let width = images.iter().map(|i| i.width).max().unwrap_or(0);
+    let height = images.iter().map(|i| i.height).max().unwrap_or(0);
+    (width, height)
+}
+
+// `scale`, or less if that would make the frames more than `MAX_SIZE` across
+pub fn fit_scale(images: &[Image], scale: usize) -> usize {
+    let (width, height) = canvas_size(images);
+    scale.min(MAX_SIZE / width.max(height).max(1)).max(1)
+}
+
+fn on_canvas(image: Image, width: usize, height: usize) -> Image {
+    if (image.width, image.height) == (width, height) {
+        return image;
+    }
+    let mut canvas = Image::new(width, height, render::BLACK);
+    for y in 0..image.height {
+        for x in 0..image.width {
+            canvas.set(x, y, image.pixels[y * image.width + x]);
+        }
+    }
+    canvas
+}
+
+// frame_00000.ppm, frame_00001.ppm, ... in `dir`, each blown up by `scale`
+pub fn write_ppm_frames(dir: &Path, images: &[Image], scale: usize) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    for (n, image) in images.iter().enumerate() {
+        image
+            .scaled(scale)
+            .write_file(&dir.join(format!("frame_{n:05}.ppm")))?;
+    }
+    Ok(())
+}
+
+// A looping GIF blown up by `scale`, showing each frame for `delay_ms`
+pub fn write_gif(out: impl Write, images: &[Image], scale: usize, delay_ms: u16) -> io::Result<()> {
+    let (width, height) = canvas_size(images);
+    let (width, height) = (width * scale, height * scale);
+    let mut encoder =
+        gif::Encoder::new(out, width as u16, height as u16, &[]).map_err(io::Error::other)?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(io::Error::other)?;
+    for image in images {
+        let pixels = on_canvas(image.scaled(scale), width, height)
+            .pixels
+            .concat();
+        let mut frame = gif::Frame::from_rgb_speed(width as u16, height as u16, &pixels, 10);
+        frame.delay = delay_ms / 10;
+        encoder.write_frame(&frame).map_err(io::Error::other)?;
+    }
+    Ok(())
+}
+
+// Two pixels per character: the top one is the text color of a '▀', and
+// the bottom one the background color behind it
+pub fn half_blocks(image: &Image) -> String {
+    let mut text = String::new();
+    let pixel = |x, y| image.get(x, y).unwrap_or(render::BLACK);
+    for y in (0..image.height).step_by(2) {
+        for x in 0..image.width {
+            let ([r, g, b], [r2, g2, b2]): (Rgb, Rgb) = (pixel(x, y), pixel(x, y + 1));
+            text.push_str(&format!("\x1b[38;2;{r};{g};{b}m\x1b[48;2;{r2};{g2};{b2}m▀"));
+        }
+        text.push_str("\x1b[0m\n");
+    }
+    text
+}
+
+// Play the frames back in the terminal blown up by `scale`, `fps` frames per
+// second
+pub fn replay(out: &mut impl Write, images: &[Image], scale: usize, fps: f64) -> io::Result<()> {
+    let pause = Duration::from_secs_f64(1.0 / fps);
+    for image in images {
+        // back to the top left corner and clear the screen
+        write!(out, "\x1b[H\x1b[2J{}", half_blocks(&image.scaled(scale)))?;
+        out.flush()?;
+        thread::sleep(pause);
+    }
+    Ok(())
+}
+
+// Save and/or replay the frames of a simulation, as asked for in the
+// environment, with every pixel blown up to a `scale` x `scale` square (or
+// smaller, to keep within `MAX_SIZE`). Like `render::save`, any problems are
+// only reported.
+pub fn save(name: &str, images: &[Image], scale: usize) {
+    if images.is_empty() {
+        return;
+    }
+    let scale = fit_scale(images, scale);
+    if let Some(dir) = render::render_dir() {
+        let gif_path = dir.join(format!("{name}.gif"));
+        let saved = fs::create_dir_all(&dir)
+            .and_then(|_| fs::File::create(&gif_path))
+            .and_then(|file| write_gif(io::BufWriter::new(file), images, scale, 50))
+            .and_then(|_| write_ppm_frames(&dir.join(name), images, scale));
+        match saved {
+            Ok(()) => eprintln!("Saved {} frames as {}", images.len(), gif_path.display()),
+            Err(e) => eprintln!("Could not save {}: {e}", gif_path.display()),
+        }
+    }
+    if let Some(fps) = replay_fps() {
+        if let Err(e) = replay(&mut io::stderr(), images, scale, fps) {
+            eprintln!("Could not replay {name}: {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetch::tests::temp_dir;
+
+    fn frame(n: usize) -> Image {
+        let mut image = Image::new(4, 3, render::BLACK);
+        image.set(n % 4, 1, render::RED);
+        image
+    }
+
+    #[test]
+    fn test_recorder() {
+        let mut recorder = Recorder::new().limit(4);
+        for n in 0..10 {
+            recorder.record(|| n);
+        }
+        // thinned out twice: after steps 4 and 8
+        assert_eq!(recorder.frames(), &[0, 4, 8]);
+        recorder.push(|| 9);
+        assert_eq!(recorder.frames().last(), Some(&9));
+
+        let mut disabled = Recorder::disabled();
+        disabled.record(|| panic!("shouldn't draw a frame that isn't kept"));
+        disabled.push(|| panic!("shouldn't draw a frame that isn't kept"));
+        assert!(disabled.frames().is_empty());
+        assert!(!disabled.is_enabled());
+    }
+
+    #[test]
+    fn test_gif() {
+        let images: Vec<Image> = (0..3)
+            .map(frame)
+            .chain([Image::new(2, 2, render::WHITE)])
+            .collect();
+        let mut data = Vec::new();
+        write_gif(&mut data, &images, 2, 100).unwrap();
+        let mut decoder = gif::DecodeOptions::new()
+            .read_info(data.as_slice())
+            .unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (8, 6));
+        let mut count = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(frame.delay, 10);
+            count += 1;
+        }
+        assert_eq!(count, 4);
+    }
+
+    #[test]
+    fn test_fit_scale() {
+        let small = [frame(0)];
+        assert_eq!(fit_scale(&small, 8), 8);
+        let big = [Image::new(300, 120, render::BLACK)];
+        assert_eq!(fit_scale(&big, 8), 2);
+        // never smaller than a pixel per pixel
+        let huge = [Image::new(2000, 10, render::BLACK)];
+        assert_eq!(fit_scale(&huge, 8), 1);
+    }
+
+    #[test]
+    fn test_ppm_frames() {
+        let dir = temp_dir("frames");
+        let images: Vec<Image> = (0..3).map(frame).collect();
+        write_ppm_frames(&dir, &images, 1).unwrap();
+        let ppm = fs::read(dir.join("frame_00002.ppm")).unwrap();
+        assert!(ppm.starts_with(b"P6\n4 3\n255\n"));
+        assert!(!dir.join("frame_00003.ppm").exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_replay() {
+        let text = half_blocks(&frame(1));
+        // 3 rows of pixels fit in 2 lines of text
+        assert_eq!(text.lines().count(), 2);
+        assert!(text.contains("\x1b[38;2;0;0;0m\x1b[48;2;220;50;47m▀"));
+        let mut out = Vec::new();
+        replay(&mut out, &[frame(0), frame(1)], 1, 1000.0).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out.matches("\x1b[2J").count(), 2);
+    }
+}
@@ -0,0 +1,346 @@
+/*
+A small register machine for the puzzles where the input is a program.
+
+The machine has 26 registers, `a` to `z`, all starting at zero, and runs a
+program one instruction at a time until the program counter falls off either
+end. Each instruction takes some number of cycles, and a hook is called
+*during* every cycle, with the registers as they are before the instruction
+finishes, which is when most puzzles want to peek at them.
+
+The instructions are anything that implements `Instruction`. `Op` covers the
+usual ones: day 10's `noop` & `addx`, and the `cpy`, `inc`, `dec`, `jnz` & `jmp`
+that other years use. A new opcode is a variant in `Op`, its mnemonic in
+`Op::parse`, and its effect in `Op::execute` (plus its cost in `Op::cycles`
+if it isn't 1); a puzzle with an instruction set of its own can have its own
+enum instead.
+*/
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+pub type Word = i64;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ParseError {
+    UnknownOpcode(String),
+    BadRegister(String),
+    BadOperand(String),
+    WrongOperands {
+        opcode: String,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::UnknownOpcode(opcode) => write!(f, "unknown opcode `{opcode}`"),
+            ParseError::BadRegister(name) => write!(f, "`{name}` isn't a register"),
+            ParseError::BadOperand(operand) => {
+                write!(f, "`{operand}` isn't a number or a register")
+            }
+            ParseError::WrongOperands {
+                opcode,
+                expected,
+                found,
+            } => write!(f, "`{opcode}` takes {expected} operands, not {found}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// A parse error, and which line of the program it's on (from 1)
+#[derive(Debug, PartialEq, Clone)]
+pub struct ProgramError {
+    pub line: usize,
+    pub error: ParseError,
+}
+
+impl fmt::Display for ProgramError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.error)
+    }
+}
+
+impl std::error::Error for ProgramError {}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Registers([Word; 26]);
+
+fn register_index(name: char) -> usize {
+    match name {
+        'a'..='z' => name as usize - 'a' as usize,
+        _ => panic!("`{name}` isn't a register"),
+    }
+}
+
+impl Index<char> for Registers {
+    type Output = Word;
+
+    fn index(&self, name: char) -> &Word {
+        &self.0[register_index(name)]
+    }
+}
+
+impl IndexMut<char> for Registers {
+    fn index_mut(&mut self, name: char) -> &mut Word {
+        &mut self.0[register_index(name)]
+    }
+}
+
+pub fn parse_register(word: &str) -> Result<char, ParseError> {
+    match word.chars().collect::<Vec<_>>()[..] {
+        [name @ 'a'..='z'] => Ok(name),
+        _ => Err(ParseError::BadRegister(word.to_string())),
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Operand {
+    Register(char),
+    Value(Word),
+}
+
+impl Operand {
+    pub fn parse(word: &str) -> Result<Operand, ParseError> {
+        match (word.parse(), parse_register(word)) {
+            (Ok(value), _) => Ok(Operand::Value(value)),
+            (_, Ok(name)) => Ok(Operand::Register(name)),
+            _ => Err(ParseError::BadOperand(word.to_string())),
+        }
+    }
+
+    pub fn value(&self, registers: &Registers) -> Word {
+        match self {
+            Operand::Register(name) => registers[*name],
+            Operand::Value(value) => *value,
+        }
+    }
+}
+
+pub trait Instruction: Sized {
+    // from the words of one line, e.g. ["addx", "-3"]
+    fn parse(words: &[&str]) -> Result<Self, ParseError>;
+
+    fn cycles(&self) -> usize {
+        1
+    }
+
+    // Carry it out, and say how far to move the program counter: 1 to go on
+    // to the next instruction, anything else to jump
+    fn execute(&self, registers: &mut Registers) -> Word;
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Op {
+    Noop,
+    // add to register x
+    Addx(Word),
+    // copy a value or register into a register
+    Cpy(Operand, char),
+    Inc(char),
+    Dec(char),
+    // jump by the second operand, if the first isn't zero
+    Jnz(Operand, Operand),
+    Jmp(Operand),
+}
+
+// Check how many operands an instruction has
+fn operands<'a, const N: usize>(words: &[&'a str]) -> Result<[&'a str; N], ParseError> {
+    words[1..]
+        .try_into()
+        .map_err(|_| ParseError::WrongOperands {
+            opcode: words[0].to_string(),
+            expected: N,
+            found: words.len() - 1,
+        })
+}
+
+impl Instruction for Op {
+    fn parse(words: &[&str]) -> Result<Op, ParseError> {
+        let op = match words[0] {
+            "noop" => {
+                operands::<0>(words)?;
+                Op::Noop
+            }
+            "addx" => {
+                let [n] = operands(words)?;
+                let n = n
+                    .parse()
+                    .map_err(|_| ParseError::BadOperand(n.to_string()))?;
+                Op::Addx(n)
+            }
+            "cpy" => {
+                let [from, to] = operands(words)?;
+                Op::Cpy(Operand::parse(from)?, parse_register(to)?)
+            }
+            "inc" => Op::Inc(parse_register(operands::<1>(words)?[0])?),
+            "dec" => Op::Dec(parse_register(operands::<1>(words)?[0])?),
+            "jnz" => {
+                let [test, offset] = operands(words)?;
+                Op::Jnz(Operand::parse(test)?, Operand::parse(offset)?)
+            }
+            "jmp" => Op::Jmp(Operand::parse(operands::<1>(words)?[0])?),
+            opcode => return Err(ParseError::UnknownOpcode(opcode.to_string())),
+        };
+        Ok(op)
+    }
+
+    fn cycles(&self) -> usize {
+        match self {
+            Op::Addx(_) => 2,
+            _ => 1,
+        }
+    }
+
+    fn execute(&self, registers: &mut Registers) -> Word {
+        match self {
+            Op::Noop => (),
+            Op::Addx(n) => registers['x'] += n,
+            Op::Cpy(from, to) => registers[*to] = from.value(registers),
+            Op::Inc(name) => registers[*name] += 1,
+            Op::Dec(name) => registers[*name] -= 1,
+            Op::Jnz(test, offset) if test.value(registers) != 0 => return offset.value(registers),
+            Op::Jnz(..) => (),
+            Op::Jmp(offset) => return offset.value(registers),
+        }
+        1
+    }
+}
+
+// One instruction per line; blank lines are skipped
+pub fn parse_program<I: Instruction>(source: &str) -> Result<Vec<I>, ProgramError> {
+    source
+        .lines()
+        .enumerate()
+        .map(|(n, line)| (n, line.split_whitespace().collect::<Vec<_>>()))
+        .filter(|(_, words)| !words.is_empty())
+        .map(|(n, words)| I::parse(&words).map_err(|error| ProgramError { line: n + 1, error }))
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Cpu<I> {
+    pub program: Vec<I>,
+    pub registers: Registers,
+    // index of the next instruction; outside of the program once it's halted
+    pub pc: Word,
+    // how many cycles have finished
+    pub cycle: usize,
+}
+
+impl<I: Instruction> Cpu<I> {
+    pub fn new(program: Vec<I>) -> Cpu<I> {
+        Cpu {
+            program,
+            registers: Registers::default(),
+            pc: 0,
+            cycle: 0,
+        }
+    }
+
+    pub fn parse(source: &str) -> Result<Cpu<I>, ProgramError> {
+        Ok(Cpu::new(parse_program(source)?))
+    }
+
+    // start with something other than zero in a register
+    pub fn with_register(mut self, name: char, value: Word) -> Cpu<I> {
+        self.registers[name] = value;
+        self
+    }
+
+    pub fn halted(&self) -> bool {
+        !(0..self.program.len() as Word).contains(&self.pc)
+    }
+
+    // Run one instruction, calling `hook(cycle, registers)` for each of its
+    // cycles (numbered from 1). False if the program had already halted.
+    pub fn step(&mut self, hook: &mut impl FnMut(usize, &Registers)) -> bool {
+        if self.halted() {
+            return false;
+        }
+        let instruction = &self.program[self.pc as usize];
+        for _ in 0..instruction.cycles() {
+            self.cycle += 1;
+            hook(self.cycle, &self.registers);
+        }
+        self.pc += instruction.execute(&mut self.registers);
+        true
+    }
+
+    // Run until the program halts. A program that loops forever never will,
+    // so use `step` for those.
+    pub fn run(&mut self, mut hook: impl FnMut(usize, &Registers)) {
+        while self.step(&mut hook) {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let program: Vec<Op> = parse_program("noop\naddx -13\n\ncpy 41 a\njnz a -2").unwrap();
+        assert_eq!(
+            program,
+            vec![
+                Op::Noop,
+                Op::Addx(-13),
+                Op::Cpy(Operand::Value(41), 'a'),
+                Op::Jnz(Operand::Register('a'), Operand::Value(-2)),
+            ]
+        );
+        let error = |source| parse_program::<Op>(source).unwrap_err();
+        assert_eq!(
+            error("noop\nfoo 3"),
+            ProgramError {
+                line: 2,
+                error: ParseError::UnknownOpcode("foo".to_string())
+            }
+        );
+        assert_eq!(
+            error("addx").to_string(),
+            "line 1: `addx` takes 1 operands, not 0"
+        );
+        assert_eq!(
+            error("inc A").error,
+            ParseError::BadRegister("A".to_string())
+        );
+        assert_eq!(
+            error("addx x").error,
+            ParseError::BadOperand("x".to_string())
+        );
+    }
+
+    #[test]
+    fn test_cycles() {
+        let mut cpu: Cpu<Op> = Cpu::parse("noop\naddx 3\naddx -5").unwrap();
+        cpu = cpu.with_register('x', 1);
+        let mut during = Vec::new();
+        cpu.run(|cycle, registers| during.push((cycle, registers['x'])));
+        assert_eq!(during, vec![(1, 1), (2, 1), (3, 1), (4, 4), (5, 4)]);
+        assert_eq!(cpu.registers['x'], -1);
+        assert_eq!(cpu.cycle, 5);
+        assert!(cpu.halted());
+        assert!(!cpu.step(&mut |_, _| panic!("halted")));
+    }
+
+    #[test]
+    fn test_jumps() {
+        // 2016 day 12's example, which leaves 42 in a
+        let source = "cpy 41 a\ninc a\ninc a\ndec a\njnz a 2\ndec a";
+        let mut cpu: Cpu<Op> = Cpu::parse(source).unwrap();
+        cpu.run(|_, _| ());
+        assert_eq!(cpu.registers['a'], 42);
+        assert_eq!(cpu.pc, 6);
+
+        // count b down to zero, adding to c each time
+        let source = "cpy 3 b\ninc c\ndec b\njnz b -2\njmp 5";
+        let mut cpu: Cpu<Op> = Cpu::parse(source).unwrap();
+        cpu.run(|_, _| ());
+        assert_eq!((cpu.registers['b'], cpu.registers['c']), (0, 3));
+        assert_eq!(cpu.cycle, 1 + 3 * 3 + 1);
+    }
+}
@@ -0,0 +1,350 @@
+/*
+Points and directions on a 2D plane.
+
+Coordinates are screen-style, the same way round as the rows of a `Grid`:
+`x` grows to the right and `y` grows downwards, so `Up` is towards `y = 0`.
+Rotations are named for how they look on screen with that orientation.
+*/
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    // same order as `neighbors_lateral`: behind, in front, above, below
+    pub const LATERAL: [Direction; 4] = [
+        Direction::Left,
+        Direction::Right,
+        Direction::Up,
+        Direction::Down,
+    ];
+    pub const DIAGONAL: [Direction; 4] = [
+        Direction::UpLeft,
+        Direction::UpRight,
+        Direction::DownLeft,
+        Direction::DownRight,
+    ];
+    pub const ALL: [Direction; 8] = [
+        Direction::Left,
+        Direction::Right,
+        Direction::Up,
+        Direction::Down,
+        Direction::UpLeft,
+        Direction::UpRight,
+        Direction::DownLeft,
+        Direction::DownRight,
+    ];
+    // all eight, going clockwise from `Up`
+    const CLOCKWISE: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    pub fn offset(&self) -> (isize, isize) {
+        // (rows, columns) moved by one step
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+            Direction::UpLeft => (-1, -1),
+            Direction::UpRight => (-1, 1),
+            Direction::DownLeft => (1, -1),
+            Direction::DownRight => (1, 1),
+        }
+    }
+
+    // one step in this direction, as a vector
+    pub fn vector<T: From<i8>>(&self) -> Point2D<T> {
+        let (dy, dx) = self.offset();
+        Point2D::new(T::from(dx as i8), T::from(dy as i8))
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        Direction::DIAGONAL.contains(self)
+    }
+
+    // turn clockwise by `eighths` of a full turn; negative turns anticlockwise
+    pub fn rotate(self, eighths: i32) -> Direction {
+        let pos = Direction::CLOCKWISE
+            .iter()
+            .position(|d| *d == self)
+            .unwrap() as i32;
+        Direction::CLOCKWISE[(pos + eighths).rem_euclid(8) as usize]
+    }
+
+    pub fn turn_right(self) -> Direction {
+        self.rotate(2)
+    }
+
+    pub fn turn_left(self) -> Direction {
+        self.rotate(-2)
+    }
+
+    pub fn opposite(self) -> Direction {
+        self.rotate(4)
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = String;
+
+    fn try_from(c: char) -> Result<Direction, String> {
+        match c {
+            'U' | '^' | '↑' => Ok(Direction::Up),
+            'D' | 'v' | '↓' => Ok(Direction::Down),
+            'L' | '<' | '←' => Ok(Direction::Left),
+            'R' | '>' | '→' => Ok(Direction::Right),
+            '↖' => Ok(Direction::UpLeft),
+            '↗' => Ok(Direction::UpRight),
+            '↙' => Ok(Direction::DownLeft),
+            '↘' => Ok(Direction::DownRight),
+            _ => Err(format!("'{c}' is not a direction")),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = String;
+
+    // a single letter or arrow, or a pair of them for diagonals, e.g. "UL" or "v>"
+    fn from_str(s: &str) -> Result<Direction, String> {
+        let dirs = s
+            .chars()
+            .map(Direction::try_from)
+            .collect::<Result<Vec<Direction>, String>>()?;
+        let vertical = |d: &Direction| matches!(d, Direction::Up | Direction::Down);
+        match dirs[..] {
+            [dir] => Ok(dir),
+            [a, b] if a.is_diagonal() || b.is_diagonal() || vertical(&a) == vertical(&b) => {
+                Err(format!("\"{s}\" is not a direction"))
+            }
+            [a, b] => {
+                let v: Point2D<i8> = a.vector() + b.vector();
+                Direction::DIAGONAL
+                    .into_iter()
+                    .find(|d| d.vector() == v)
+                    .ok_or_else(|| format!("\"{s}\" is not a direction"))
+            }
+            _ => Err(format!("\"{s}\" is not a direction")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point2D<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point2D<T> {
+    pub fn new(x: T, y: T) -> Point2D<T> {
+        Point2D { x, y }
+    }
+}
+
+// absolute difference, without needing a signed type
+fn abs_diff<T: Copy + PartialOrd + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>> Point2D<T> {
+    pub fn manhattan(&self, other: &Point2D<T>) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    // the number of king's moves between two points
+    pub fn chebyshev(&self, other: &Point2D<T>) -> T {
+        let (dx, dy) = (abs_diff(self.x, other.x), abs_diff(self.y, other.y));
+        if dx > dy {
+            dx
+        } else {
+            dy
+        }
+    }
+}
+
+impl<T: Copy + Neg<Output = T>> Point2D<T> {
+    // quarter turns about the origin
+    pub fn rotate_right(self) -> Point2D<T> {
+        Point2D::new(-self.y, self.x)
+    }
+
+    pub fn rotate_left(self) -> Point2D<T> {
+        Point2D::new(self.y, -self.x)
+    }
+}
+
+impl<T: Copy + PartialOrd + From<i8>> Point2D<T> {
+    // -1, 0 or 1 for each coordinate: one step of at most one unit towards `self`
+    pub fn signum(self) -> Point2D<T> {
+        let sign = |v: T| {
+            if v > T::from(0) {
+                T::from(1)
+            } else if v < T::from(0) {
+                T::from(-1)
+            } else {
+                T::from(0)
+            }
+        };
+        Point2D::new(sign(self.x), sign(self.y))
+    }
+}
+
+impl<T: Copy + Add<Output = T> + From<i8>> Point2D<T> {
+    pub fn step(self, direction: Direction) -> Point2D<T> {
+        self + direction.vector()
+    }
+
+    pub fn neighbors4(self) -> impl Iterator<Item = Point2D<T>> {
+        Direction::LATERAL.into_iter().map(move |d| self.step(d))
+    }
+
+    pub fn neighbors8(self) -> impl Iterator<Item = Point2D<T>> {
+        Direction::ALL.into_iter().map(move |d| self.step(d))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point2D<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point2D<T> {
+    type Output = Point2D<T>;
+
+    fn add(self, other: Point2D<T>) -> Point2D<T> {
+        Point2D::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point2D<T> {
+    type Output = Point2D<T>;
+
+    fn sub(self, other: Point2D<T>) -> Point2D<T> {
+        Point2D::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point2D<T> {
+    type Output = Point2D<T>;
+
+    fn mul(self, scale: T) -> Point2D<T> {
+        Point2D::new(self.x * scale, self.y * scale)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point2D<T> {
+    type Output = Point2D<T>;
+
+    fn neg(self) -> Point2D<T> {
+        Point2D::new(-self.x, -self.y)
+    }
+}
+
+impl<T: Add<Output = T> + From<i8>> Add<Direction> for Point2D<T> {
+    type Output = Point2D<T>;
+
+    fn add(self, direction: Direction) -> Point2D<T> {
+        self + direction.vector()
+    }
+}
+
+impl<T: Copy + Add<Output = T>> AddAssign for Point2D<T> {
+    fn add_assign(&mut self, other: Point2D<T>) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Copy + Sub<Output = T>> SubAssign for Point2D<T> {
+    fn sub_assign(&mut self, other: Point2D<T>) {
+        *self = *self - other;
+    }
+}
+
+impl<T: Copy + Add<Output = T> + From<i8>> AddAssign<Direction> for Point2D<T> {
+    fn add_assign(&mut self, direction: Direction) {
+        *self = *self + direction;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_direction() {
+        assert_eq!("U".parse(), Ok(Direction::Up));
+        assert_eq!("<".parse(), Ok(Direction::Left));
+        assert_eq!("↓".parse(), Ok(Direction::Down));
+        assert_eq!(Direction::try_from('R'), Ok(Direction::Right));
+        assert_eq!("UL".parse(), Ok(Direction::UpLeft));
+        assert_eq!("v>".parse(), Ok(Direction::DownRight));
+        assert!("UD".parse::<Direction>().is_err());
+        assert!("X".parse::<Direction>().is_err());
+        assert!("".parse::<Direction>().is_err());
+    }
+
+    #[test]
+    fn test_rotate_direction() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.rotate(1), Direction::UpLeft);
+        assert_eq!(Direction::UpLeft.rotate(-3), Direction::Down);
+        assert_eq!(Direction::DownRight.opposite(), Direction::UpLeft);
+        for dir in Direction::ALL {
+            let v: Point2D<i32> = dir.vector();
+            assert_eq!(dir.turn_right().vector(), v.rotate_right());
+            assert_eq!(dir.turn_left().vector(), v.rotate_left());
+        }
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let p = Point2D::new(3, -2);
+        assert_eq!(p + Point2D::new(1, 1), Point2D::new(4, -1));
+        assert_eq!(p - Point2D::new(1, 1), Point2D::new(2, -3));
+        assert_eq!(p * 2, Point2D::new(6, -4));
+        assert_eq!(-p, Point2D::new(-3, 2));
+        assert_eq!(p + Direction::Up, Point2D::new(3, -3));
+        let mut q = p;
+        q += Direction::DownLeft;
+        q -= Point2D::new(0, 1);
+        assert_eq!(q, Point2D::new(2, -2));
+        assert_eq!(Point2D::new(5, -7).signum(), Point2D::new(1, -1));
+        assert_eq!(p.neighbors8().count(), 8);
+        assert!(p.neighbors4().all(|n| n.manhattan(&p) == 1));
+    }
+
+    #[test]
+    fn test_distance() {
+        let (a, b) = (Point2D::new(0, 5), Point2D::new(1, 2));
+        assert_eq!(a.manhattan(&b), 4);
+        assert_eq!(a.chebyshev(&b), 3);
+        let (a, b) = (Point2D::<usize>::new(2, 0), Point2D::new(0, 3));
+        assert_eq!(a.manhattan(&b), 5);
+        assert_eq!(b.chebyshev(&a), 3);
+    }
+}
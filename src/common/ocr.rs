@@ -0,0 +1,200 @@
+/*
+Read the block letters that some puzzles draw as their answer.
+
+Advent of Code uses two fonts: letters 6 pixels high in cells 5 pixels wide,
+and letters 10 pixels high in cells 8 pixels wide, with the letters starting
+at the left edge of the screen. Neither has every letter, only the ones that
+have turned up in puzzles. The font is picked by the height of the picture,
+which is cut into cells, and each cell is compared with the letters after
+trimming the blank columns off both sides. That way a letter narrower than
+the rest (`I`) is read wherever it sits in its cell, and one that's wider
+(`Y` fills its whole cell, so it touches the next letter) is read as well.
+
+The fonts below are drawn with a blank column between each letter, so they're
+split into letters at the blank columns instead.
+*/
+use super::Grid;
+use std::ops::Range;
+
+const SMALL: (&str, [&str; 6]) = (
+    "ABCEFGHIJKLOPRSUYZ",
+    [
+        ".##..###...##..####.####..##..#..#.###...##.#..#.#.....##..###..###...###.#..#.#...#.####",
+        "#..#.#..#.#..#.#....#....#..#.#..#..#.....#.#.#..#....#..#.#..#.#..#.#....#..#.#...#....#",
+        "#..#.###..#....###..###..#....####..#.....#.##...#....#..#.#..#.#..#.#....#..#..#.#....#.",
+        "####.#..#.#....#....#....#.##.#..#..#.....#.#.#..#....#..#.###..###...##..#..#...#....#..",
+        "#..#.#..#.#..#.#....#....#..#.#..#..#..#..#.#.#..#....#..#.#....#.#.....#.#..#...#...#...",
+        "#..#.###...##..####.#.....###.#..#.###..##..#..#.####..##..#....#..#.###...##....#...####",
+    ],
+);
+
+const LARGE: (&str, [&str; 10]) = (
+    "ABCEFGHJKLNPRXZ",
+    [
+        "..##....#####....####...######..######...####...#....#.....###..#....#..#.......#....#..#####...#####...#....#..######",
+        ".#..#...#....#..#....#..#.......#.......#....#..#....#......#...#...#...#.......##...#..#....#..#....#..#....#.......#",
+        "#....#..#....#..#.......#.......#.......#.......#....#......#...#..#....#.......##...#..#....#..#....#...#..#........#",
+        "#....#..#....#..#.......#.......#.......#.......#....#......#...#.#.....#.......#.#..#..#....#..#....#...#..#.......#.",
+        "#....#..#####...#.......#####...#####...#.......######......#...##......#.......#.#..#..#####...#####.....##.......#..",
+        "######..#....#..#.......#.......#.......#..###..#....#......#...##......#.......#..#.#..#.......#..#......##......#...",
+        "#....#..#....#..#.......#.......#.......#....#..#....#......#...#.#.....#.......#..#.#..#.......#...#....#..#....#....",
+        "#....#..#....#..#.......#.......#.......#....#..#....#..#...#...#..#....#.......#...##..#.......#...#....#..#...#.....",
+        "#....#..#....#..#....#..#.......#.......#...##..#....#..#...#...#...#...#.......#...##..#.......#....#..#....#..#.....",
+        "#....#..#####....####...######..#........###.#..#....#...###....#....#..######..#....#..#.......#....#..#....#..######",
+    ],
+);
+
+// The letters in a picture, or None if it isn't 6 or 10 pixels high (not
+// counting blank rows above and below) or any of the letters isn't known
+pub fn read(screen: &Grid<bool>) -> Option<String> {
+    let rows: Vec<&[bool]> = screen
+        .data
+        .chunks(screen.num_cols.max(1))
+        .skip_while(|row| !row.contains(&true))
+        .collect();
+    let blank_below = rows
+        .iter()
+        .rev()
+        .take_while(|row| !row.contains(&true))
+        .count();
+    let rows = &rows[..rows.len() - blank_below];
+    let (letters, font, cell) = match rows.len() {
+        6 => (SMALL.0, &SMALL.1[..], 5),
+        10 => (LARGE.0, &LARGE.1[..], 8),
+        _ => return None,
+    };
+    let known = glyphs(font);
+    // up to the last lit column, so a screen that isn't full doesn't end in
+    // blank cells
+    let width = (0..screen.num_cols)
+        .rfind(|col| rows.iter().any(|row| row[*col]))
+        .map_or(0, |col| col + 1);
+    (0..width)
+        .step_by(cell)
+        .map(|left| {
+            let glyph = trim(rows, left..(left + cell).min(width));
+            let n = known.iter().position(|k| *k == glyph)?;
+            letters.chars().nth(n)
+        })
+        .collect()
+}
+
+// The columns in `cols` as rows of '#' and '.', without any blank columns on
+// either side
+fn trim<R: AsRef<[P]>, P: Lit>(rows: &[R], cols: Range<usize>) -> Vec<String> {
+    let lit = |row: &R, col: usize| row.as_ref().get(col).is_some_and(|p| p.lit());
+    let used: Vec<usize> = cols
+        .filter(|col| rows.iter().any(|row| lit(row, *col)))
+        .collect();
+    let cols = match (used.first(), used.last()) {
+        (Some(first), Some(last)) => *first..*last + 1,
+        _ => 0..0,
+    };
+    rows.iter()
+        .map(|row| {
+            cols.clone()
+                .map(|col| if lit(row, col) { '#' } else { '.' })
+                .collect()
+        })
+        .collect()
+}
+
+// Split a font into letters at the blank columns between them
+fn glyphs<R: AsRef<[P]>, P: Lit>(rows: &[R]) -> Vec<Vec<String>> {
+    let width = rows.iter().map(|row| row.as_ref().len()).max().unwrap_or(0);
+    let blank = |col: usize| trim(rows, col..col + 1)[0].is_empty();
+    let mut glyphs = Vec::new();
+    let mut start = None;
+    for col in 0..=width {
+        match (start, blank(col)) {
+            (None, false) => start = Some(col),
+            (Some(from), true) => {
+                glyphs.push(trim(rows, from..col));
+                start = None;
+            }
+            _ => (),
+        }
+    }
+    glyphs
+}
+
+// a pixel, either from a screen or from one of the fonts
+trait Lit {
+    fn lit(&self) -> bool;
+}
+
+impl Lit for bool {
+    fn lit(&self) -> bool {
+        *self
+    }
+}
+
+impl Lit for u8 {
+    fn lit(&self) -> bool {
+        *self == b'#'
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    fn picture(text: &str) -> Grid<bool> {
+        Grid::parse(text.trim_end(), |c| c == '#').unwrap()
+    }
+
+    #[test]
+    fn test_small() {
+        // laid out like the day 10 CRT: 40 columns, with each letter in a
+        // cell 5 wide, so `Y` runs right up to the `Z` after it
+        let screen = picture(indoc! {"
+            ........................................
+            .##..###..####.#..#..###.#...#####.#....
+            #..#.#..#.#....#..#...#..#...#...#.#....
+            #..#.###..###..####...#...#.#...#..#....
+            ####.#..#.#....#..#...#....#...#...#....
+            #..#.#..#.#....#..#...#....#..#....#....
+            #..#.###..####.#..#..###...#..####.####.
+        "});
+        assert_eq!(screen.num_cols, 40);
+        assert_eq!(read(&screen), Some("ABEHIYZL".to_string()));
+        // Y then Z, with nothing after them
+        let screen = picture(indoc! {"
+            #...#####
+            #...#...#
+            .#.#...#.
+            ..#...#..
+            ..#..#...
+            ..#..####
+        "});
+        assert_eq!(read(&screen), Some("YZ".to_string()));
+    }
+
+    #[test]
+    fn test_large() {
+        let screen = picture(indoc! {"
+            #....#..#....#..######
+            #....#..##...#.......#
+            .#..#...##...#.......#
+            .#..#...#.#..#......#.
+            ..##....#.#..#.....#..
+            ..##....#..#.#....#...
+            .#..#...#..#.#...#....
+            .#..#...#...##..#.....
+            #....#..#...##..#.....
+            #....#..#....#..######
+        "});
+        assert_eq!(read(&screen), Some("XNZ".to_string()));
+    }
+
+    #[test]
+    fn test_unreadable() {
+        // not a letter
+        let screen = picture("####\n#..#\n####\n#..#\n#..#\n####");
+        assert_eq!(read(&screen), None);
+        // the wrong height
+        assert_eq!(read(&picture("#.#\n.#.\n#.#")), None);
+        assert_eq!(read(&picture("...\n...")), None);
+    }
+}
@@ -0,0 +1,221 @@
+/*
+Turn grids into pictures: PPM or PNG image files, or colored terminal output.
+
+A grid becomes an `Image` by choosing a color for each cell, and each cell can
+be blown up to a square of pixels so small grids are still visible. Days save
+their pictures with `save`, which does nothing unless the `AOC_RENDER`
+environment variable names a directory to put them in, e.g.
+`AOC_RENDER=renders cargo run 2022 14`.
+*/
+use super::Grid;
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+pub const RENDER_VAR: &str = "AOC_RENDER";
+
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+pub const RED: Rgb = [220, 50, 47];
+pub const GREEN: Rgb = [0, 153, 0];
+pub const GOLD: Rgb = [255, 255, 102];
+pub const SAND: Rgb = [237, 201, 175];
+pub const ROCK: Rgb = [110, 110, 110];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Image {
+        Image {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    pub fn from_grid<T>(grid: &Grid<T>, color: impl Fn(&T) -> Rgb) -> Image {
+        Image {
+            width: grid.num_cols,
+            height: grid.num_rows,
+            pixels: grid.data.iter().map(color).collect(),
+        }
+    }
+
+    // draw over one pixel, e.g. to mark a path; off the edge is ignored
+    pub fn set(&mut self, x: usize, y: usize, color: Rgb) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = color;
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<Rgb> {
+        (x < self.width && y < self.height).then(|| self.pixels[y * self.width + x])
+    }
+
+    // every pixel becomes a `factor` x `factor` square
+    pub fn scaled(&self, factor: usize) -> Image {
+        let mut scaled = Image::new(self.width * factor, self.height * factor, BLACK);
+        for y in 0..scaled.height {
+            for x in 0..scaled.width {
+                scaled.pixels[y * scaled.width + x] =
+                    self.pixels[(y / factor) * self.width + x / factor];
+            }
+        }
+        scaled
+    }
+
+    // binary PPM (P6), which any image viewer can open
+    pub fn write_ppm(&self, out: &mut impl Write) -> io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        out.write_all(&self.pixels.concat())
+    }
+
+    pub fn write_png(&self, out: impl Write) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(out, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels.concat())?;
+        writer.finish()?;
+        Ok(())
+    }
+
+    // PNG or PPM, going by the file's extension
+    pub fn write_file(&self, path: &Path) -> io::Result<()> {
+        let png = match path.extension().and_then(|e| e.to_str()) {
+            Some("png") => true,
+            Some("ppm") => false,
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{} should end in .png or .ppm", path.display()),
+                ))
+            }
+        };
+        let mut out = BufWriter::new(File::create(path)?);
+        if png {
+            self.write_png(&mut out)?;
+        } else {
+            self.write_ppm(&mut out)?;
+        }
+        out.flush()
+    }
+}
+
+// Where renders go, if they've been asked for
+pub fn render_dir() -> Option<PathBuf> {
+    env::var_os(RENDER_VAR)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+}
+
+// Save the image as `name` (e.g. "2022.14.png") in the render directory, if
+// there is one; `image` is only called to draw it then. Rendering is only ever
+// a side show, so failing to save is reported rather than stopping the puzzle.
+pub fn save(name: &str, image: impl FnOnce() -> Image) {
+    let Some(dir) = render_dir() else {
+        return;
+    };
+    let path = dir.join(name);
+    match fs::create_dir_all(&dir).and_then(|_| image().write_file(&path)) {
+        Ok(()) => eprintln!("Saved {}", path.display()),
+        Err(e) => eprintln!("Could not save {}: {e}", path.display()),
+    }
+}
+
+// A grid as 24-bit colored text, with a character & color for each cell
+pub fn ansi<T>(grid: &Grid<T>, style: impl Fn(&T) -> (char, Rgb)) -> String {
+    let mut text = String::new();
+    for row in grid.data.chunks(grid.num_cols.max(1)) {
+        for cell in row {
+            let (c, [r, g, b]) = style(cell);
+            text.push_str(&format!("\x1b[38;2;{r};{g};{b}m{c}"));
+        }
+        text.push_str("\x1b[0m\n");
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetch::tests::temp_dir;
+
+    fn checkerboard() -> Grid<bool> {
+        Grid::parse("#.\n.#\n#.", |c| c == '#').unwrap()
+    }
+
+    #[test]
+    fn test_image() {
+        let mut image = Image::from_grid(&checkerboard(), |on| if *on { WHITE } else { BLACK });
+        assert_eq!((image.width, image.height), (2, 3));
+        assert_eq!(image.get(1, 1), Some(WHITE));
+        image.set(1, 1, RED);
+        image.set(5, 5, RED);
+        assert_eq!(image.get(1, 1), Some(RED));
+        assert_eq!(image.get(2, 0), None);
+
+        let scaled = image.scaled(3);
+        assert_eq!((scaled.width, scaled.height), (6, 9));
+        assert_eq!(scaled.get(0, 2), Some(WHITE));
+        assert_eq!(scaled.get(3, 0), Some(BLACK));
+        assert_eq!(scaled.get(5, 5), Some(RED));
+    }
+
+    #[test]
+    fn test_ppm() {
+        let image = Image::from_grid(&checkerboard(), |on| if *on { GOLD } else { BLACK });
+        let mut ppm = Vec::new();
+        image.write_ppm(&mut ppm).unwrap();
+        assert!(ppm.starts_with(b"P6\n2 3\n255\n"));
+        assert_eq!(ppm.len(), 11 + 2 * 3 * 3);
+        assert_eq!(ppm[11..14], GOLD);
+    }
+
+    #[test]
+    fn test_png() {
+        let image = Image::from_grid(&checkerboard(), |on| if *on { GREEN } else { RED });
+        let mut png_data = Vec::new();
+        image.write_png(&mut png_data).unwrap();
+        // read it back in to check it's a real PNG
+        let decoder = png::Decoder::new(png_data.as_slice());
+        let mut reader = decoder.read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (2, 3));
+        assert_eq!(pixels[..6], [GREEN, RED].concat());
+
+        let dir = temp_dir("render");
+        fs::create_dir_all(&dir).unwrap();
+        image.write_file(&dir.join("board.png")).unwrap();
+        image.write_file(&dir.join("board.ppm")).unwrap();
+        assert!(image.write_file(&dir.join("board.jpg")).is_err());
+        assert_eq!(fs::read(dir.join("board.png")).unwrap(), png_data);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_ansi() {
+        let text = ansi(
+            &checkerboard(),
+            |on| {
+                if *on {
+                    ('#', RED)
+                } else {
+                    ('.', WHITE)
+                }
+            },
+        );
+        assert_eq!(text.lines().count(), 3);
+        assert!(text.starts_with("\x1b[38;2;220;50;47m#\x1b[38;2;255;255;255m."));
+        assert!(text.ends_with("\x1b[0m\n"));
+    }
+}
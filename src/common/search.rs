@@ -0,0 +1,260 @@
+/*
+Generic graph searches: breadth first, Dijkstra and A*.
+
+Nodes can be anything hashable, and the graph is given as a function from a
+node to its neighbors (with the cost of getting there, for the weighted
+searches). Every search can start from several nodes at once, and stops as
+soon as it reaches a node where `is_goal` is true; with `|_| false` it visits
+everything reachable instead, which gives the distance to every node.
+*/
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+#[derive(Debug, Clone)]
+pub struct Search<N, C> {
+    distances: HashMap<N, C>,
+    parents: HashMap<N, N>,
+    goal: Option<N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Search<N, C> {
+    fn new() -> Search<N, C> {
+        Search {
+            distances: HashMap::new(),
+            parents: HashMap::new(),
+            goal: None,
+        }
+    }
+
+    // the goal the search stopped at, if it found one
+    pub fn goal(&self) -> Option<&N> {
+        self.goal.as_ref()
+    }
+
+    pub fn goal_distance(&self) -> Option<C> {
+        self.goal.as_ref().and_then(|goal| self.distance(goal))
+    }
+
+    pub fn goal_path(&self) -> Option<Vec<N>> {
+        self.goal.as_ref().and_then(|goal| self.path_to(goal))
+    }
+
+    // distance from the nearest start, for every node the search reached
+    pub fn distances(&self) -> &HashMap<N, C> {
+        &self.distances
+    }
+
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.distances.get(node).copied()
+    }
+
+    // the path from a start node to `node`, including both ends
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        if !self.distances.contains_key(node) {
+            return None;
+        }
+        let mut path = vec![node.clone()];
+        while let Some(parent) = self.parents.get(path.last().unwrap()) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+// Every step costs one
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Search<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut search = Search::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if !search.distances.contains_key(&start) {
+            search.distances.insert(start.clone(), 0);
+            queue.push_back(start);
+        }
+    }
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+        let distance = search.distances[&node] + 1;
+        for next in neighbors(&node) {
+            if !search.distances.contains_key(&next) {
+                search.distances.insert(next.clone(), distance);
+                search.parents.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+    search
+}
+
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, neighbors, |_| C::default(), is_goal)
+}
+
+// `heuristic` must never overestimate the remaining cost to the goal,
+// otherwise the path found might not be the shortest
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut search = Search::new();
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        search.distances.insert(start.clone(), C::default());
+        queue.push(Queued {
+            priority: heuristic(&start),
+            cost: C::default(),
+            node: start,
+        });
+    }
+    while let Some(Queued { cost, node, .. }) = queue.pop() {
+        if search.distances.get(&node).is_some_and(|best| cost > *best) {
+            // already found a cheaper way here
+            continue;
+        }
+        if is_goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+        for (next, step) in neighbors(&node) {
+            let cost = cost + step;
+            if search.distances.get(&next).is_none_or(|best| cost < *best) {
+                search.distances.insert(next.clone(), cost);
+                search.parents.insert(next.clone(), node.clone());
+                queue.push(Queued {
+                    priority: cost + heuristic(&next),
+                    cost,
+                    node: next,
+                });
+            }
+        }
+    }
+    search
+}
+
+// Ordered so that `BinaryHeap` pops the lowest priority first
+struct Queued<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> Ord for Queued<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<N, C: Ord> PartialOrd for Queued<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> PartialEq for Queued<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for Queued<N, C> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::geom::Point2D;
+
+    // a 5 x 5 room with a wall down the middle, open at the bottom
+    fn open(p: &Point2D<i32>) -> bool {
+        (0..5).contains(&p.x) && (0..5).contains(&p.y) && !(p.x == 2 && p.y < 4)
+    }
+
+    fn moves(p: &Point2D<i32>) -> impl Iterator<Item = Point2D<i32>> {
+        p.neighbors4().filter(open)
+    }
+
+    #[test]
+    fn test_bfs() {
+        let start = Point2D::new(0, 0);
+        let goal = Point2D::new(4, 0);
+        let search = bfs([start], moves, |p| *p == goal);
+        assert_eq!(search.goal(), Some(&goal));
+        assert_eq!(search.goal_distance(), Some(12));
+        let path = search.goal_path().unwrap();
+        assert_eq!(path.len(), 13);
+        assert_eq!(path[0], start);
+        assert!(path.windows(2).all(|w| w[0].manhattan(&w[1]) == 1));
+
+        // no goal: every open square gets a distance
+        let search = bfs([start], moves, |_| false);
+        assert_eq!(search.goal(), None);
+        assert_eq!(search.distances().len(), 21);
+        assert_eq!(search.distance(&Point2D::new(2, 4)), Some(6));
+        assert_eq!(search.distance(&Point2D::new(2, 0)), None);
+        assert_eq!(search.path_to(&Point2D::new(2, 0)), None);
+
+        // from either side of the wall, whichever is nearest
+        let search = bfs([start, goal], moves, |_| false);
+        assert_eq!(search.distance(&Point2D::new(3, 0)), Some(1));
+        assert_eq!(search.distance(&Point2D::new(2, 4)), Some(6));
+        assert_eq!(search.path_to(&goal), Some(vec![goal]));
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        // going down costs 10, everything else costs 1
+        let weighted = |p: &Point2D<i32>| {
+            let p = *p;
+            moves(&p)
+                .map(move |n| (n, if n.y > p.y { 10 } else { 1 }))
+                .collect::<Vec<_>>()
+        };
+        let search = dijkstra([Point2D::new(0, 0)], weighted, |p| p.y == 4);
+        assert_eq!(search.goal_distance(), Some(40));
+        let search = dijkstra([Point2D::new(0, 4)], weighted, |p| p.x == 4);
+        assert_eq!(search.goal_distance(), Some(4));
+        assert_eq!(search.goal(), Some(&Point2D::new(4, 4)));
+    }
+
+    #[test]
+    fn test_astar() {
+        let start = Point2D::new(0, 0);
+        let goal = Point2D::new(4, 0);
+        let with_cost = |p: &Point2D<i32>| moves(p).map(|n| (n, 1));
+        let search = astar([start], with_cost, |p| p.manhattan(&goal), |p| *p == goal);
+        assert_eq!(search.goal_distance(), Some(12));
+        assert_eq!(search.goal_path().unwrap().last(), Some(&goal));
+        // a good heuristic shouldn't need to look at everything
+        let everything = dijkstra([start], with_cost, |_| false);
+        assert!(search.distances().len() <= everything.distances().len());
+        assert_eq!(everything.distance(&goal), Some(12));
+    }
+}
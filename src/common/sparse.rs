@@ -0,0 +1,250 @@
+/*
+Grids addressed by `Point2D` coordinates that don't have to start at zero.
+
+`SparseGrid` only stores the cells that have been set, in a hash map, which
+suits a few things scattered over a big area. `OffsetGrid` is a dense `Grid`
+that keeps track of where its top left corner is, and grows in whichever
+direction it needs to (negative coordinates included) when a cell outside of
+it is set; anything it hasn't stored reads as the fill value.
+*/
+use super::geom::Point2D;
+use super::Grid;
+use std::collections::HashMap;
+
+pub type Point = Point2D<i32>;
+
+// Smallest box holding every point, as (top left, bottom right) inclusive
+fn extent(points: impl IntoIterator<Item = Point>) -> Option<(Point, Point)> {
+    points.into_iter().fold(None, |bounds, p| match bounds {
+        None => Some((p, p)),
+        Some((lo, hi)) => Some((
+            Point::new(lo.x.min(p.x), lo.y.min(p.y)),
+            Point::new(hi.x.max(p.x), hi.y.max(p.y)),
+        )),
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid::default()
+    }
+
+    pub fn get(&self, p: &Point) -> Option<&T> {
+        self.cells.get(p)
+    }
+
+    pub fn insert(&mut self, p: Point, value: T) -> Option<T> {
+        self.cells.insert(p, value)
+    }
+
+    pub fn remove(&mut self, p: &Point) -> Option<T> {
+        self.cells.remove(p)
+    }
+
+    pub fn contains(&self, p: &Point) -> bool {
+        self.cells.contains_key(p)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Point, &T)> {
+        self.cells.iter()
+    }
+
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        extent(self.cells.keys().copied())
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    // the same cells packed into a dense grid, with `fill` everywhere else
+    pub fn to_dense(&self, fill: T) -> OffsetGrid<T> {
+        let mut dense = OffsetGrid::new(fill);
+        if let Some((lo, hi)) = self.bounds() {
+            // set the corners first so it only has to grow once
+            dense.set(lo, dense.fill.clone());
+            dense.set(hi, dense.fill.clone());
+        }
+        for (p, value) in self.iter() {
+            dense.set(*p, value.clone());
+        }
+        dense
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        SparseGrid {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OffsetGrid<T> {
+    grid: Grid<T>,
+    // the coordinates of the grid's row 0, column 0
+    origin: Point,
+    fill: T,
+    // the box around every cell that's been set
+    used: Option<(Point, Point)>,
+}
+
+impl<T: Clone> OffsetGrid<T> {
+    pub fn new(fill: T) -> OffsetGrid<T> {
+        OffsetGrid {
+            grid: Grid::new(0, 0, fill.clone()),
+            origin: Point::default(),
+            fill,
+            used: None,
+        }
+    }
+
+    fn index(&self, p: &Point) -> Option<usize> {
+        let d = *p - self.origin;
+        if d.x < 0 || d.y < 0 {
+            return None;
+        }
+        self.grid.ind(d.y as usize, d.x as usize).ok()
+    }
+
+    pub fn get(&self, p: &Point) -> &T {
+        match self.index(p) {
+            Some(i) => &self.grid.data[i],
+            None => &self.fill,
+        }
+    }
+
+    pub fn set(&mut self, p: Point, value: T) {
+        if self.index(&p).is_none() {
+            self.grow_to(p);
+        }
+        let i = self.index(&p).unwrap();
+        self.grid.data[i] = value;
+        self.used = extent(
+            self.used
+                .into_iter()
+                .flat_map(|(lo, hi)| [lo, hi])
+                .chain([p]),
+        );
+    }
+
+    // box around every cell that's been set, None if nothing has
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        self.used
+    }
+
+    // the whole area that's been allocated so far
+    pub fn allocated(&self) -> (Point, usize, usize) {
+        (self.origin, self.grid.num_rows, self.grid.num_cols)
+    }
+
+    fn grow_to(&mut self, p: Point) {
+        let (rows, cols) = (self.grid.num_rows as i32, self.grid.num_cols as i32);
+        let (mut lo, mut hi) = if self.grid.data.is_empty() {
+            (p, p)
+        } else {
+            (self.origin, self.origin + Point::new(cols - 1, rows - 1))
+        };
+        // leave some room on the side that grew, so that setting cells one
+        // at a time in the same direction doesn't copy everything each time
+        let (slack_x, slack_y) = (cols / 2, rows / 2);
+        if p.x < lo.x {
+            lo.x = p.x - slack_x;
+        }
+        if p.x > hi.x {
+            hi.x = p.x + slack_x;
+        }
+        if p.y < lo.y {
+            lo.y = p.y - slack_y;
+        }
+        if p.y > hi.y {
+            hi.y = p.y + slack_y;
+        }
+
+        let size = hi - lo + Point::new(1, 1);
+        let mut grown = Grid::new(size.y as usize, size.x as usize, self.fill.clone());
+        let d = self.origin - lo;
+        for (i, value) in std::mem::take(&mut self.grid.data).into_iter().enumerate() {
+            let (row, col) = (i as i32 / cols + d.y, i as i32 % cols + d.x);
+            grown.data[(row * size.x + col) as usize] = value;
+        }
+        self.grid = grown;
+        self.origin = lo;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sparse_grid() {
+        let mut sparse: SparseGrid<char> = [(Point::new(-3, 2), 'a'), (Point::new(500, -7), 'b')]
+            .into_iter()
+            .collect();
+        assert_eq!(sparse.len(), 2);
+        assert_eq!(sparse.get(&Point::new(-3, 2)), Some(&'a'));
+        assert_eq!(sparse.get(&Point::new(0, 0)), None);
+        assert_eq!(
+            sparse.bounds(),
+            Some((Point::new(-3, -7), Point::new(500, 2)))
+        );
+        assert_eq!(sparse.insert(Point::new(-3, 2), 'c'), Some('a'));
+        assert_eq!(sparse.remove(&Point::new(500, -7)), Some('b'));
+        assert!(!sparse.contains(&Point::new(500, -7)));
+        assert_eq!(
+            sparse.bounds(),
+            Some((Point::new(-3, 2), Point::new(-3, 2)))
+        );
+        assert_eq!(SparseGrid::<char>::new().bounds(), None);
+
+        let dense = sparse.to_dense('.');
+        assert_eq!(*dense.get(&Point::new(-3, 2)), 'c');
+        assert_eq!(*dense.get(&Point::new(-4, 2)), '.');
+    }
+
+    #[test]
+    fn test_offset_grid() {
+        let mut grid = OffsetGrid::new(0);
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(*grid.get(&Point::new(10, 10)), 0);
+        grid.set(Point::new(500, 0), 1);
+        assert_eq!(grid.allocated(), (Point::new(500, 0), 1, 1));
+        // grow in every direction, keeping what's already there
+        grid.set(Point::new(498, 4), 2);
+        grid.set(Point::new(-5, -2), 3);
+        grid.set(Point::new(503, 9), 4);
+        for (p, value) in [((500, 0), 1), ((498, 4), 2), ((-5, -2), 3), ((503, 9), 4)] {
+            assert_eq!(*grid.get(&Point::new(p.0, p.1)), value);
+        }
+        assert_eq!(*grid.get(&Point::new(499, 0)), 0);
+        assert_eq!(*grid.get(&Point::new(-100, 0)), 0);
+        assert_eq!(
+            grid.bounds(),
+            Some((Point::new(-5, -2), Point::new(503, 9)))
+        );
+        let (origin, rows, cols) = grid.allocated();
+        assert!(origin.x <= -5 && origin.y <= -2);
+        assert!(origin.x + cols as i32 > 503 && origin.y + rows as i32 > 9);
+    }
+}
@@ -1,5 +1,22 @@
-pub fn main(contents: String) {
-    println!("Hello AoC!")
+use crate::solver::{Answer, Solver};
+
+pub struct DayX;
+
+impl Solver for DayX {
+    type Input = String;
+
+    fn parse(&self, contents: String) -> String {
+        contents
+    }
+
+    fn part_1(&self, _contents: &String) -> Answer {
+        eprintln!("Hello AoC!");
+        Answer::Unsolved
+    }
+
+    fn part_2(&self, _contents: &String) -> Answer {
+        Answer::Unsolved
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,321 @@
+/*
+Download puzzle inputs from adventofcode.com into `inputs/{year}.{day}`.
+
+The session token is the value of the `session` cookie from a logged-in browser.
+It's read from the `AOC_SESSION` environment variable, or failing that from
+the first line of `~/.config/aoc/session`.
+
+Inputs never change once a puzzle is out, so a file that's already there is
+never downloaded again.
+
+The puzzle description can also be fetched, to save its example as
+`inputs/{year}.{day}.test` and the example answers in the year's answer file.
+*/
+use crate::answers::AnswerBook;
+use crate::puzzle;
+use crate::solver::Part;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+// AoC asks automated tools to identify themselves and where to find their source
+pub const USER_AGENT: &str = "github.com/blairfrandeen/2022-AoC (aoc_2022 fetch)";
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+#[derive(Debug, PartialEq)]
+pub enum Fetched {
+    Downloaded(PathBuf),
+    Cached(PathBuf),
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Example {
+    // only set when the file was written
+    pub input: Option<PathBuf>,
+    pub answers: Vec<(Part, String)>,
+}
+
+fn has_contents(path: &Path) -> bool {
+    fs::metadata(path).map(|m| m.len() > 0).unwrap_or(false)
+}
+
+// The input in `dir`, if it's already been downloaded
+pub fn cached_input(year: i32, day: i32, dir: &Path) -> Option<PathBuf> {
+    Some(dir.join(format!("{year}.{day}"))).filter(|path| has_contents(path))
+}
+
+pub fn session_file() -> Option<PathBuf> {
+    env::var("HOME")
+        .ok()
+        .map(|home| Path::new(&home).join(".config/aoc/session"))
+}
+
+pub fn find_session(from_env: Option<String>, file: Option<&Path>) -> Result<String, String> {
+    if let Some(session) = from_env.filter(|s| !s.trim().is_empty()) {
+        return Ok(session.trim().to_string());
+    }
+    let from_file = file
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|contents| contents.lines().next().map(|l| l.trim().to_string()))
+        .filter(|s| !s.is_empty());
+    match from_file {
+        Some(session) => Ok(session),
+        None => Err(format!(
+            "No session token found. Set {SESSION_VAR}, or save it in {}",
+            file.map(|p| p.display().to_string())
+                .unwrap_or("~/.config/aoc/session".to_string())
+        )),
+    }
+}
+
+pub struct Fetcher {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Fetcher {
+    pub fn new(base_url: &str, session: &str) -> Fetcher {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build();
+        Fetcher {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent,
+        }
+    }
+
+    pub fn get(&self, path: &str) -> Result<String, String> {
+        let url = format!("{}{path}", self.base_url);
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+        Fetcher::text(&url, response)
+    }
+
+    pub fn post(&self, path: &str, form: &[(&str, &str)]) -> Result<String, String> {
+        let url = format!("{}{path}", self.base_url);
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(form);
+        Fetcher::text(&url, response)
+    }
+
+    fn text(url: &str, response: Result<ureq::Response, ureq::Error>) -> Result<String, String> {
+        match response {
+            Ok(response) => response
+                .into_string()
+                .map_err(|e| format!("Could not read response from {url}: {e}")),
+            Err(ureq::Error::Status(code, _)) => Err(match code {
+                400 | 500 => format!("{url} returned {code}: is the session token out of date?"),
+                404 => format!("{url} returned 404: the puzzle isn't available yet"),
+                _ => format!("{url} returned {code}"),
+            }),
+            Err(e) => Err(format!("Could not reach {url}: {e}")),
+        }
+    }
+
+    pub fn fetch_input(&self, year: i32, day: i32, dir: &Path) -> Result<Fetched, String> {
+        if let Some(path) = cached_input(year, day, dir) {
+            return Ok(Fetched::Cached(path));
+        }
+        let path = dir.join(format!("{year}.{day}"));
+        let contents = self.get(&format!("/{year}/day/{day}/input"))?;
+        fs::create_dir_all(dir).map_err(|e| format!("Could not create {}: {e}", dir.display()))?;
+        fs::write(&path, contents)
+            .map_err(|e| format!("Could not write {}: {e}", path.display()))?;
+        Ok(Fetched::Downloaded(path))
+    }
+
+    pub fn fetch_example(
+        &self,
+        year: i32,
+        day: i32,
+        dir: &Path,
+        book: &mut AnswerBook,
+    ) -> Result<Example, String> {
+        // The page changes once part 1 is solved, so it's always fetched,
+        // but anything already saved is left alone.
+        let html = self.get(&format!("/{year}/day/{day}"))?;
+        let mut example = Example::default();
+
+        let path = dir.join(format!("{year}.{day}.test"));
+        if let (false, Some(input)) = (has_contents(&path), puzzle::examples(&html).first()) {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("Could not create {}: {e}", dir.display()))?;
+            fs::write(&path, input)
+                .map_err(|e| format!("Could not write {}: {e}", path.display()))?;
+            example.input = Some(path);
+        }
+        for (part, answer) in Part::BOTH.into_iter().zip(puzzle::example_answers(&html)) {
+            let record = book.get_mut(day, part);
+            if record.example.is_none() {
+                record.example = Some(answer.clone());
+                example.answers.push((part, answer));
+            }
+        }
+        Ok(example)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+
+    // Stand-in for adventofcode.com: answers each connection with the next
+    // canned (status, body), and hands back the requests it saw.
+    pub fn mock_server(responses: Vec<(u16, String)>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = len.trim().parse().unwrap();
+                    }
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    request.push_str(&line);
+                }
+                let mut payload = vec![0; content_length];
+                std::io::Read::read_exact(&mut reader, &mut payload).unwrap();
+                request.push_str(&String::from_utf8(payload).unwrap());
+                requests.push(request);
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+            requests
+        });
+        (base_url, handle)
+    }
+
+    pub fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_fetch_input() {
+        let (base_url, server) = mock_server(vec![(200, "1000\n2000\n".to_string())]);
+        let dir = temp_dir("fetch");
+        let fetcher = Fetcher::new(&base_url, "abc123");
+
+        let path = dir.join("2022.1");
+        assert_eq!(
+            fetcher.fetch_input(2022, 1, &dir),
+            Ok(Fetched::Downloaded(path.clone()))
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1000\n2000\n");
+        assert_eq!(cached_input(2022, 1, &dir), Some(path.clone()));
+        assert_eq!(cached_input(2022, 2, &dir), None);
+        // the server only answers once, so this has to come from the file
+        assert_eq!(
+            fetcher.fetch_input(2022, 1, &dir),
+            Ok(Fetched::Cached(path))
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2022/day/1/input HTTP/1.1"));
+        assert!(requests[0].contains("Cookie: session=abc123"));
+        assert!(requests[0].contains(&format!("User-Agent: {USER_AGENT}")));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_errors() {
+        let (base_url, server) = mock_server(vec![
+            (404, "Not Found".to_string()),
+            (400, "Please log in".to_string()),
+        ]);
+        let dir = temp_dir("fetch_errors");
+        let fetcher = Fetcher::new(&base_url, "abc123");
+        assert!(fetcher
+            .fetch_input(2022, 25, &dir)
+            .unwrap_err()
+            .contains("isn't available yet"));
+        assert!(fetcher
+            .fetch_input(2022, 25, &dir)
+            .unwrap_err()
+            .contains("session token"));
+        // nothing gets written for a failed download
+        assert!(!dir.join("2022.25").exists());
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_fetch_example() {
+        let html = crate::puzzle::tests::PUZZLE_HTML.to_string();
+        let (base_url, server) = mock_server(vec![(200, html.clone()), (200, html)]);
+        let dir = temp_dir("fetch_example");
+        let fetcher = Fetcher::new(&base_url, "abc123");
+        let mut book = AnswerBook::default();
+
+        let example = fetcher.fetch_example(2022, 1, &dir, &mut book).unwrap();
+        let path = dir.join("2022.1.test");
+        assert_eq!(example.input, Some(path.clone()));
+        assert_eq!(
+            example.answers,
+            vec![
+                (Part::One, "24000".to_string()),
+                (Part::Two, "45000".to_string())
+            ]
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1000\n2000\n\n4000\n");
+        assert_eq!(
+            book.get(1, Part::Two).unwrap().example,
+            Some("45000".to_string())
+        );
+
+        // a second fetch doesn't change what's already there
+        fs::write(&path, "edited").unwrap();
+        let example = fetcher.fetch_example(2022, 1, &dir, &mut book).unwrap();
+        assert_eq!(example, Example::default());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "edited");
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2022/day/1 HTTP/1.1"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_find_session() {
+        let dir = temp_dir("session");
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("session");
+        assert!(find_session(None, Some(&file)).is_err());
+        fs::write(&file, "from_file\n").unwrap();
+        assert_eq!(find_session(None, Some(&file)), Ok("from_file".to_string()));
+        assert_eq!(
+            find_session(Some("from_env".to_string()), Some(&file)),
+            Ok("from_env".to_string())
+        );
+        assert_eq!(
+            find_session(Some(" ".to_string()), Some(&file)),
+            Ok("from_file".to_string())
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,27 +1,157 @@
 /*
 Guidelines for adding a new day:
-- Source code for new day's puzzle should be in `src/day_x.rs`
-- That code should have one public `main `function that can be called
-    from here
-- Add the module to the list below
+- Run `cargo run new YYYY X` to create `src/yYYYY/day_x.rs` from the template
+- That code should have one public struct `DayX` implementing `solver::Solver`
+- Modules for each year and day, and their registration, are generated by
+    build.rs from the files in `src/`, so nothing else needs editing
 - Utility functions can be broken out later
 */
 
 #![feature(iter_next_chunk)]
 #![feature(test)]
 extern crate test;
+use answers::AnswerBook;
+use solver::{Part, Registry};
 use std::env;
 use std::fs;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::process;
+use std::str::FromStr;
+
+enum Command {
+    List,
+    Run(Config),
+    Check(Config),
+    Fetch(i32, DaySelection, bool),
+    New(i32, i32),
+    Submit(i32, i32, Part),
+}
+
+impl Command {
+    fn build(args: impl Iterator<Item = String>) -> Result<Command, &'static str> {
+        let mut args = args.peekable();
+        args.next(); // skip first arg
+        match args.peek().map(|arg| arg.as_str()) {
+            Some("list") => Ok(Command::List),
+            Some("check") => {
+                args.next();
+                let config = Config::build(args)?;
+                // answers are only known for the real inputs and the examples
+                if config.input.is_some() || config.test.as_ref().is_some_and(|t| t != "test") {
+                    return Err("check runs against the real inputs, or the examples with `test`");
+                }
+                Ok(Command::Check(config))
+            }
+            Some("fetch") => {
+                args.next();
+                let year = match args.next().map(|y| y.parse::<i32>()) {
+                    Some(Ok(year)) => year,
+                    _ => return Err("fetch needs a year"),
+                };
+                let days = match args.next().map(|d| d.parse::<DaySelection>()) {
+                    Some(days) => days?,
+                    None => return Err("fetch needs a day or range of days"),
+                };
+                match args.next().as_deref() {
+                    Some("--examples") => Ok(Command::Fetch(year, days, true)),
+                    Some(_) => Err("fetch only takes --examples after the days"),
+                    None => Ok(Command::Fetch(year, days, false)),
+                }
+            }
+            Some("new") => {
+                args.next();
+                match (args.next(), args.next()) {
+                    (Some(year), Some(day)) => match (year.parse(), day.parse()) {
+                        (Ok(year), Ok(day)) => Ok(Command::New(year, day)),
+                        _ => Err("Invalid year or day"),
+                    },
+                    _ => Err("new needs a year and a day"),
+                }
+            }
+            Some("submit") => {
+                args.next();
+                match (args.next(), args.next(), args.next()) {
+                    (Some(year), Some(day), Some(part)) => match (year.parse(), day.parse()) {
+                        (Ok(year), Ok(day)) => Ok(Command::Submit(year, day, part.parse()?)),
+                        _ => Err("Invalid year or day"),
+                    },
+                    _ => Err("submit needs a year, day and part"),
+                }
+            }
+            Some("run") => {
+                args.next();
+                Ok(Command::Run(Config::build(args)?))
+            }
+            _ => Ok(Command::Run(Config::build(args)?)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum DaySelection {
+    One(i32),
+    Range(RangeInclusive<i32>),
+    All,
+}
+
+impl DaySelection {
+    fn days(&self, registry: &Registry, year: i32) -> Vec<i32> {
+        // a single day is always run, so that a missing day is reported;
+        // ranges only pick up the days that have been registered
+        let registered = registry
+            .list()
+            .filter(|(yr, _)| *yr == year)
+            .map(|(_, day)| day);
+        match self {
+            DaySelection::One(day) => vec![*day],
+            DaySelection::Range(range) => registered.filter(|day| range.contains(day)).collect(),
+            DaySelection::All => registered.collect(),
+        }
+    }
+}
+
+impl FromStr for DaySelection {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<DaySelection, Self::Err> {
+        let parse_day = |d: &str| d.trim().parse::<i32>().map_err(|_| "Invalid day range");
+        match s.split_once('-') {
+            Some((first, last)) => Ok(DaySelection::Range(parse_day(first)?..=parse_day(last)?)),
+            None => Ok(DaySelection::One(parse_day(s)?)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Format {
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Format, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err("Format must be text or json"),
+        }
+    }
+}
 
 struct Config {
     year: i32,
-    day: i32,
+    days: DaySelection,
     test: Option<String>,
+    part: Option<Part>,
+    format: Format,
+    input: Option<String>,
 }
 
 impl Config {
     fn build(mut args: impl Iterator<Item = String>) -> Result<Config, &'static str> {
-        args.next(); // skip first arg
         let year = match args.next() {
             Some(year) => match year.parse::<i32>() {
                 Ok(yr) => yr,
@@ -29,20 +159,71 @@ impl Config {
             },
             None => return Err("No year specified"),
         };
-        let day = match args.next() {
-            Some(day) => match day.parse::<i32>() {
-                Ok(dy) => dy,
-                Err(e) => panic!("Invalid Day Specified: {day} - {e}"),
-            },
+
+        let mut days = None;
+        let mut test = None;
+        let mut part = None;
+        let mut format = Format::Text;
+        let mut input = None;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--part" => match args.next() {
+                    Some(p) => part = Some(p.parse::<Part>()?),
+                    None => return Err("No part specified"),
+                },
+                "--format" => match args.next() {
+                    Some(f) => format = f.parse::<Format>()?,
+                    None => return Err("No format specified"),
+                },
+                "--input" => match args.next() {
+                    Some(i) => input = Some(i),
+                    None => return Err("No input path specified"),
+                },
+                "--all" => days = Some(DaySelection::All),
+                "--days" => match args.next() {
+                    Some(d) => days = Some(d.parse::<DaySelection>()?),
+                    None => return Err("No days specified"),
+                },
+                _ if days.is_none() => match arg.parse::<i32>() {
+                    Ok(dy) => days = Some(DaySelection::One(dy)),
+                    Err(e) => panic!("Invalid Day Specified: {arg} - {e}"),
+                },
+                _ => test = Some(arg),
+            }
+        }
+        let days = match days {
+            Some(days) => days,
             None => return Err("No day specified"),
         };
+        // one input can only be for one day
+        if input.is_some() && !matches!(days, DaySelection::One(_)) {
+            return Err("--input can only be used with a single day");
+        }
 
-        let test = args.next();
+        Ok(Config {
+            year,
+            days,
+            test,
+            part,
+            format,
+            input,
+        })
+    }
 
-        Ok(Config { year, day, test })
+    fn input_path(&self, day: i32) -> String {
+        match &self.input {
+            Some(path) => path.clone(),
+            None => runner::input_path(self.year, day, self.test.as_deref()),
+        }
     }
 }
 
+fn registry() -> Registry {
+    let mut registry = Registry::default();
+    register_years(&mut registry);
+    registry
+}
+
 /*
    Read the input file, and return a vector of string slices.
    Remove the last elemenet if it's empty (generated by trailing newline)
@@ -57,39 +238,217 @@ fn _read_input(file_path: &str) -> Vec<String> {
 }
 
 fn main() {
-    let config = Config::build(env::args()).unwrap();
-    let test_marker = match config.test {
-        Some(t) => format!(".{t}"),
-        None => "".to_string(),
+    let registry = registry();
+    match Command::build(env::args()).unwrap() {
+        Command::List => {
+            for (year, day) in registry.list() {
+                println!("{year} day {day}");
+            }
+        }
+        Command::Run(config) => run(&registry, config),
+        Command::Check(config) => check(&registry, config),
+        Command::Fetch(year, days, examples) => {
+            if let Err(e) = fetch_inputs(year, days, examples) {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        }
+        Command::Submit(year, day, part) => {
+            if let Err(e) = submit_answer(&registry, year, day, part) {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        }
+        Command::New(year, day) => match scaffold::new_day(Path::new("."), year, day) {
+            Ok(created) => {
+                for path in created {
+                    println!("Created {}", path.display());
+                }
+                println!("Download the input with `cargo run fetch {year} {day}`");
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        },
+    }
+}
+
+fn fetch_inputs(year: i32, days: DaySelection, examples: bool) -> Result<(), String> {
+    let days: Vec<i32> = match days {
+        DaySelection::One(day) => vec![day],
+        DaySelection::Range(range) => range.collect(),
+        DaySelection::All => unreachable!("fetch always names its days"),
     };
-    let input_path = format!("inputs/{}.{}{}", config.year, config.day, test_marker);
-    println!("{input_path}");
-
-    // let contents = read_input(&input_path);
-    let contents = fs::read_to_string(&input_path).unwrap();
-    match config.day {
-        16 => day_16::main(contents),
-        21 => day_21::main(contents),
-        20 => day_20::main(contents),
-        18 => day_18::main(contents),
-        15 => day_15::main(contents),
-        14 => day_14::main(contents),
-        13 => day_13::main(contents),
-        12 => day_12::main(contents),
-        11 => day_11::main(contents),
-        10 => day_10::main(contents),
-        9 => day_9::main(contents),
-        8 => day_8::main(contents),
-        7 => day_7::main(contents),
-        6 => day_6::main(contents),
-        5 => day_5::main(contents),
-        4 => day_4::main(contents),
-        3 => day_3::main(contents),
-        2 => day_2::main(contents),
-        1 => day_1::main(contents),
-        _ => panic!("Day not implemented!"),
+    // only looked for once something actually has to be downloaded
+    let mut fetcher = None;
+    let mut book = AnswerBook::load(year)?;
+    for day in days {
+        let fetched = match fetch::cached_input(year, day, Path::new("inputs")) {
+            Some(path) => fetch::Fetched::Cached(path),
+            None => connect(&mut fetcher)?.fetch_input(year, day, Path::new("inputs"))?,
+        };
+        match fetched {
+            fetch::Fetched::Downloaded(path) => println!("Downloaded {}", path.display()),
+            fetch::Fetched::Cached(path) => println!("Already have {}", path.display()),
+        }
+        if examples {
+            let example =
+                connect(&mut fetcher)?.fetch_example(year, day, Path::new("inputs"), &mut book)?;
+            if let Some(path) = example.input {
+                println!("Saved example in {}", path.display());
+            }
+            for (part, answer) in example.answers {
+                println!("Example answer for part {part}: {answer}");
+            }
+        }
+    }
+    if examples {
+        book.save(year)?;
+    }
+    Ok(())
+}
+
+// The fetcher, set up with the session token the first time it's needed
+fn connect(fetcher: &mut Option<fetch::Fetcher>) -> Result<&fetch::Fetcher, String> {
+    if fetcher.is_none() {
+        let session = fetch::find_session(
+            env::var(fetch::SESSION_VAR).ok(),
+            fetch::session_file().as_deref(),
+        )?;
+        *fetcher = Some(fetch::Fetcher::new(fetch::BASE_URL, &session));
     }
+    Ok(fetcher.as_ref().expect("just set up"))
+}
+
+fn submit_answer(registry: &Registry, year: i32, day: i32, part: Part) -> Result<(), String> {
+    let input_path = runner::input_path(year, day, None);
+    let report = runner::run_day(registry, year, day, &input_path, &[part]);
+    let answer = match report.solution {
+        Some(solution) => solution.parts[0].answer.clone(),
+        None => return Err(report.error.unwrap_or_default()),
+    };
+    println!("{year} day {day} part {part}: submitting {answer}");
+
+    let session = fetch::find_session(
+        env::var(fetch::SESSION_VAR).ok(),
+        fetch::session_file().as_deref(),
+    )?;
+    let fetcher = fetch::Fetcher::new(fetch::BASE_URL, &session);
+    let mut book = AnswerBook::load(year)?;
+    let puzzle = (year, day, part);
+    let outcome = submit::submit(
+        &fetcher,
+        &mut book,
+        Path::new(submit::WAIT_FILE),
+        puzzle,
+        &answer,
+    )?;
+    book.save(year)?;
+    println!("{outcome}");
+    Ok(())
 }
+
+fn parts_to_run(part: Option<Part>) -> Vec<Part> {
+    match part {
+        Some(part) => vec![part],
+        None => Part::BOTH.to_vec(),
+    }
+}
+
+fn run(registry: &Registry, config: Config) {
+    let parts = parts_to_run(config.part);
+
+    // one JSON object per line, per day; anything the days print goes to stderr
+    if config.format == Format::Json {
+        let mut failed = false;
+        for day in config.days.days(registry, config.year) {
+            let input_path = config.input_path(day);
+            let report = runner::run_day(registry, config.year, day, &input_path, &parts);
+            failed |= report.failed();
+            println!("{}", report.to_json());
+        }
+        if failed {
+            process::exit(1);
+        }
+        return;
+    }
+
+    if let DaySelection::One(day) = config.days {
+        let input_path = config.input_path(day);
+        println!("{input_path}");
+        let report = runner::run_day(registry, config.year, day, &input_path, &parts);
+        if let Some(e) = report.error {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+        for result in report.solution.expect("no error means a solution").parts {
+            println!("Part {}: {}", result.part, result.answer);
+        }
+        return;
+    }
+
+    let mut reports = Vec::new();
+    for day in config.days.days(registry, config.year) {
+        let input_path = config.input_path(day);
+        eprintln!("Running {input_path}");
+        reports.push(runner::run_day(
+            registry,
+            config.year,
+            day,
+            &input_path,
+            &parts,
+        ));
+    }
+    print!("{}", runner::summary_table(&reports));
+    if reports.iter().any(|r| r.failed()) {
+        process::exit(1);
+    }
+}
+
+fn check(registry: &Registry, config: Config) {
+    let book = match AnswerBook::load(config.year) {
+        Ok(book) => book,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+    let parts = parts_to_run(config.part);
+
+    let mut failed = false;
+    for day in config.days.days(registry, config.year) {
+        let input_path = config.input_path(day);
+        let report = runner::run_day(registry, config.year, day, &input_path, &parts);
+        let solution = match report.solution {
+            Some(solution) => solution,
+            None => {
+                failed = true;
+                println!(
+                    "{} day {day:>2}: ERROR ({})",
+                    config.year,
+                    report.error.unwrap_or_default()
+                );
+                continue;
+            }
+        };
+        for result in solution.parts {
+            let verdict = match config.test {
+                Some(_) => book.check_example(day, result.part, &result.answer),
+                None => book.check(day, result.part, &result.answer),
+            };
+            failed |= verdict.is_fail();
+            println!(
+                "{} day {day:>2} part {}: {} - {verdict}",
+                config.year, result.part, result.answer
+            );
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -98,24 +457,132 @@ mod tests {
     fn test_read() {
         assert_eq!(_read_input("inputs/2022.1.test").len(), 14);
     }
+
+    #[test]
+    fn test_config() {
+        let args = ["aoc", "2022", "5", "test", "--part", "2"].map(String::from);
+        let config = match Command::build(args.into_iter()) {
+            Ok(Command::Run(config)) => config,
+            _ => panic!("expected a run command"),
+        };
+        assert_eq!((config.year, config.days), (2022, DaySelection::One(5)));
+        assert_eq!(config.test, Some("test".to_string()));
+        assert_eq!(config.part, Some(Part::Two));
+        assert_eq!(config.format, Format::Text);
+
+        let args = ["aoc", "list"].map(String::from);
+        assert!(matches!(
+            Command::build(args.into_iter()),
+            Ok(Command::List)
+        ));
+        let args = ["aoc", "2022", "5", "--part", "3"].map(String::from);
+        assert!(Command::build(args.into_iter()).is_err());
+
+        let args = ["aoc", "run", "2022", "--days", "1-12", "test"].map(String::from);
+        let config = match Command::build(args.into_iter()) {
+            Ok(Command::Run(config)) => config,
+            _ => panic!("expected a run command"),
+        };
+        assert_eq!(config.days, DaySelection::Range(1..=12));
+        assert_eq!(config.test, Some("test".to_string()));
+        let args = ["aoc", "2022", "--all", "--format", "json"].map(String::from);
+        match Command::build(args.into_iter()) {
+            Ok(Command::Run(config)) => assert_eq!(config.format, Format::Json),
+            _ => panic!("expected a run command"),
+        }
+        let args = ["aoc", "2022", "--all", "--format", "yaml"].map(String::from);
+        assert!(Command::build(args.into_iter()).is_err());
+
+        let args = ["aoc", "2022", "5", "--input", "-"].map(String::from);
+        let config = match Command::build(args.into_iter()) {
+            Ok(Command::Run(config)) => config,
+            _ => panic!("expected a run command"),
+        };
+        assert_eq!(config.input_path(5), "-");
+        let args = ["aoc", "2022", "5", "test"].map(String::from);
+        let config = match Command::build(args.into_iter()) {
+            Ok(Command::Run(config)) => config,
+            _ => panic!("expected a run command"),
+        };
+        assert_eq!(config.input_path(5), "inputs/2022.5.test");
+        let args = ["aoc", "2022", "--all", "--input", "mine.txt"].map(String::from);
+        assert!(Command::build(args.into_iter()).is_err());
+        let args = ["aoc", "2022"].map(String::from);
+        assert!(Command::build(args.into_iter()).is_err());
+
+        let args = ["aoc", "fetch", "2022", "1-3"].map(String::from);
+        assert!(matches!(
+            Command::build(args.into_iter()),
+            Ok(Command::Fetch(2022, DaySelection::Range(_), false))
+        ));
+        let args = ["aoc", "fetch", "2022", "5", "--examples"].map(String::from);
+        assert!(matches!(
+            Command::build(args.into_iter()),
+            Ok(Command::Fetch(2022, DaySelection::One(5), true))
+        ));
+        let args = ["aoc", "fetch", "2022"].map(String::from);
+        assert!(Command::build(args.into_iter()).is_err());
+        let args = ["aoc", "new", "2023", "1"].map(String::from);
+        assert!(matches!(
+            Command::build(args.into_iter()),
+            Ok(Command::New(2023, 1))
+        ));
+        let args = ["aoc", "submit", "2022", "13", "1"].map(String::from);
+        assert!(matches!(
+            Command::build(args.into_iter()),
+            Ok(Command::Submit(2022, 13, Part::One))
+        ));
+        let args = ["aoc", "submit", "2022", "13", "3"].map(String::from);
+        assert!(Command::build(args.into_iter()).is_err());
+        let args = ["aoc", "new", "2023"].map(String::from);
+        assert!(Command::build(args.into_iter()).is_err());
+
+        let args = ["aoc", "check", "2022", "--all"].map(String::from);
+        assert!(matches!(
+            Command::build(args.into_iter()),
+            Ok(Command::Check(_))
+        ));
+        let args = ["aoc", "check", "2022", "13", "test"].map(String::from);
+        assert!(matches!(
+            Command::build(args.into_iter()),
+            Ok(Command::Check(_))
+        ));
+        let args = ["aoc", "check", "2022", "13", "test2"].map(String::from);
+        assert!(Command::build(args.into_iter()).is_err());
+    }
+
+    #[test]
+    fn test_day_selection() {
+        let registry = registry();
+        assert_eq!("7".parse::<DaySelection>(), Ok(DaySelection::One(7)));
+        assert!("1-x".parse::<DaySelection>().is_err());
+        let days = "1-12"
+            .parse::<DaySelection>()
+            .unwrap()
+            .days(&registry, 2022);
+        assert_eq!(days, (1..=12).collect::<Vec<i32>>());
+        let days = DaySelection::All.days(&registry, 2022);
+        assert_eq!(days.len(), 19);
+        assert_eq!(days[days.len() - 1], 21);
+        assert!(DaySelection::All.days(&registry, 2023).is_empty());
+        assert_eq!(DaySelection::One(17).days(&registry, 2022), vec![17]);
+    }
+
+    #[test]
+    fn test_registry() {
+        let registry = registry();
+        assert!(registry.find(2022, 8).is_ok());
+        assert!(registry.find(2022, 17).is_err());
+        assert!(registry.find(2023, 8).is_err());
+    }
 }
+pub mod answers;
 pub mod common;
-pub mod day_1;
-pub mod day_10;
-pub mod day_11;
-pub mod day_12;
-pub mod day_13;
-pub mod day_14;
-pub mod day_15;
-pub mod day_18;
-pub mod day_2;
-pub mod day_20;
-pub mod day_21;
-pub mod day_3;
-pub mod day_4;
-pub mod day_5;
-pub mod day_6;
-pub mod day_7;
-pub mod day_8;
-pub mod day_9;
-pub mod day_16;
+pub mod fetch;
+pub mod puzzle;
+pub mod runner;
+pub mod scaffold;
+pub mod solver;
+pub mod submit;
+// `pub mod yYYYY;` for each year, and `register_years()`
+include!(concat!(env!("OUT_DIR"), "/years.rs"));
@@ -0,0 +1,113 @@
+/*
+Pull the worked example out of a puzzle description page.
+
+Each part of the puzzle is an `<article class="day-desc">`. The example input
+is a `<pre><code>` block in the first one, and the example's answer is
+(almost always) the last emphasized code in each part, e.g. `<code><em>24000</em></code>`.
+The second part only shows up on the page once the first has been solved.
+*/
+
+const ARTICLE: (&str, &str) = ("<article class=\"day-desc\">", "</article>");
+const EXAMPLE: (&str, &str) = ("<pre><code>", "</code></pre>");
+const ANSWERS: [(&str, &str); 2] = [
+    ("<code><em>", "</em></code>"),
+    ("<em><code>", "</code></em>"),
+];
+
+// Every piece of `text` between `open` and `close`, along with where it starts
+fn between<'a>(text: &'a str, (open, close): (&str, &str)) -> Vec<(usize, &'a str)> {
+    let mut found = Vec::new();
+    let mut rest = 0;
+    while let Some(start) = text[rest..].find(open).map(|i| rest + i + open.len()) {
+        let Some(end) = text[start..].find(close).map(|i| start + i) else {
+            break;
+        };
+        found.push((start, &text[start..end]));
+        rest = end + close.len();
+    }
+    found
+}
+
+// Drop any tags, and turn the entities AoC uses back into characters
+pub fn clean(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => (),
+        }
+    }
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+fn articles(html: &str) -> Vec<&str> {
+    between(html, ARTICLE).into_iter().map(|(_, a)| a).collect()
+}
+
+pub fn examples(html: &str) -> Vec<String> {
+    articles(html)
+        .iter()
+        .flat_map(|article| between(article, EXAMPLE))
+        .map(|(_, block)| clean(block))
+        .collect()
+}
+
+pub fn example_answers(html: &str) -> Vec<String> {
+    articles(html)
+        .iter()
+        .filter_map(|article| {
+            ANSWERS
+                .iter()
+                .flat_map(|tags| between(article, *tags))
+                .max_by_key(|(pos, _)| *pos)
+                .map(|(_, answer)| clean(answer))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    pub const PUZZLE_HTML: &str = indoc! {r#"
+        <main>
+        <article class="day-desc"><h2>--- Day 1: Calorie Counting ---</h2>
+        <p>For example, suppose the Elves finish writing their items' <code>Calories</code>:</p>
+        <pre><code>1000
+        2000
+
+        <em>4000</em>
+        </code></pre>
+        <p>Find the Elf carrying the most Calories: <code><em>24000</em></code>.</p>
+        </article>
+        <p>Your puzzle answer was <code>69310</code>.</p>
+        <article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+        <p>In the example above, the top three is <code>24000</code> and <em><code>45000</code></em> together.</p>
+        </article>
+        </main>
+    "#};
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(examples(PUZZLE_HTML), vec!["1000\n2000\n\n4000\n"]);
+        assert_eq!(example_answers(PUZZLE_HTML), vec!["24000", "45000"]);
+        // before part 1 is solved there's only one part
+        let part_1 = PUZZLE_HTML.split("<p>Your puzzle").next().unwrap();
+        assert_eq!(example_answers(part_1), vec!["24000"]);
+        assert!(examples("<html></html>").is_empty());
+    }
+
+    #[test]
+    fn test_clean() {
+        assert_eq!(clean("a &lt;<em>b</em>&gt; &amp;lt;"), "a <b> &lt;");
+        assert_eq!(between("[1] [2] [3", ("[", "]")), vec![(1, "1"), (5, "2")]);
+    }
+}
@@ -0,0 +1,355 @@
+/*
+Run one or more days and collect what happened.
+
+Each day runs inside `catch_unwind`, so one day panicking doesn't stop the
+rest of a `--all` run; the panic message is kept in the report instead.
+
+Inputs are read from `inputs/{year}.{day}` (or `inputs/{year}.{day}.test`)
+unless another path is given; a path of `-` reads from stdin.
+*/
+use crate::solver::{Part, Registry, Solution};
+use serde_json::{json, Value};
+use std::any::Any;
+use std::fs;
+use std::io::{self, ErrorKind, Read};
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Duration;
+
+#[derive(Debug)]
+pub struct DayReport {
+    pub year: i32,
+    pub day: i32,
+    pub input: String,
+    pub solution: Option<Solution>,
+    pub error: Option<String>,
+}
+
+impl DayReport {
+    pub fn failed(&self) -> bool {
+        self.error.is_some()
+    }
+
+    pub fn to_json(&self) -> Value {
+        // durations are whole microseconds
+        let micros = |d: Duration| d.as_micros() as u64;
+        let (parse_us, parts) = match &self.solution {
+            Some(solution) => (
+                Some(micros(solution.parse_time)),
+                solution
+                    .parts
+                    .iter()
+                    .map(|p| json!({"part": u8::from(p.part), "answer": p.answer, "time_us": micros(p.elapsed)}))
+                    .collect(),
+            ),
+            None => (None, Vec::new()),
+        };
+        json!({
+            "year": self.year,
+            "day": self.day,
+            "input": self.input,
+            "parse_us": parse_us,
+            "parts": parts,
+            "error": self.error,
+        })
+    }
+}
+
+pub fn input_path(year: i32, day: i32, test: Option<&str>) -> String {
+    match test {
+        Some(t) => format!("inputs/{year}.{day}.{t}"),
+        None => format!("inputs/{year}.{day}"),
+    }
+}
+
+pub fn read_input(year: i32, day: i32, path: &str) -> Result<String, String> {
+    if path == "-" {
+        let mut contents = String::new();
+        return match io::stdin().read_to_string(&mut contents) {
+            Ok(_) => Ok(contents),
+            Err(e) => Err(format!("Could not read input from stdin: {e}")),
+        };
+    }
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(contents),
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            let mut msg = format!("No input found for {year} day {day}: {path} does not exist.");
+            // only the default paths have a known way to get them
+            if path == input_path(year, day, None) {
+                msg.push_str(&format!(
+                    "\nDownload it with `cargo run fetch {year} {day}`, or pass --input <path>."
+                ));
+            } else if path.starts_with(&format!("{}.", input_path(year, day, None))) {
+                msg.push_str("\nCopy the example from the puzzle description into that file.");
+            }
+            Err(msg)
+        }
+        Err(e) => Err(format!("Could not read {path}: {e}")),
+    }
+}
+
+pub fn run_day(
+    registry: &Registry,
+    year: i32,
+    day: i32,
+    input_path: &str,
+    parts: &[Part],
+) -> DayReport {
+    let mut report = DayReport {
+        year,
+        day,
+        input: input_path.to_string(),
+        solution: None,
+        error: None,
+    };
+    let solver = match registry.find(year, day) {
+        Ok(solver) => solver,
+        Err(e) => {
+            report.error = Some(e);
+            return report;
+        }
+    };
+    let contents = match read_input(year, day, input_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            report.error = Some(e);
+            return report;
+        }
+    };
+    match panic::catch_unwind(AssertUnwindSafe(|| solver.solve(contents, parts))) {
+        Ok(solution) => report.solution = Some(solution),
+        Err(payload) => report.error = Some(format!("panicked: {}", panic_message(&payload))),
+    }
+    report
+}
+
+fn panic_message(payload: &Box<dyn Any + Send>) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg.to_string()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_micros();
+    if micros < 1_000 {
+        format!("{micros} µs")
+    } else if micros < 1_000_000 {
+        format!("{:.2} ms", micros as f64 / 1_000.0)
+    } else {
+        format!("{:.2} s", duration.as_secs_f64())
+    }
+}
+
+pub fn summary_table(reports: &[DayReport]) -> String {
+    // Build all of the cells first so every column can be padded to its widest entry.
+    let header: Vec<String> = ["Year", "Day", "Parse", "Part 1", "Time", "Part 2", "Time"]
+        .iter()
+        .map(|h| h.to_string())
+        .collect();
+    let mut rows: Vec<Vec<String>> = vec![header];
+    // answers that don't fit on one line get printed after the table
+    let mut notes: Vec<String> = Vec::new();
+    for report in reports {
+        let mut row = vec![report.year.to_string(), report.day.to_string()];
+        match &report.solution {
+            Some(solution) => {
+                row.push(format_duration(solution.parse_time));
+                for part in Part::BOTH {
+                    match solution.parts.iter().find(|p| p.part == part) {
+                        Some(result) => {
+                            let answer = result.answer.to_string();
+                            if answer.trim().contains('\n') {
+                                row.push("(see below)".to_string());
+                                notes.push(format!(
+                                    "{} day {} part {part}:{answer}",
+                                    report.year, report.day
+                                ));
+                            } else {
+                                row.push(answer);
+                            }
+                            row.push(format_duration(result.elapsed));
+                        }
+                        None => row.extend(["-".to_string(), "-".to_string()]),
+                    }
+                }
+            }
+            None => {
+                row.push("FAILED".to_string());
+                notes.push(format!(
+                    "{} day {}: {}",
+                    report.year,
+                    report.day,
+                    report.error.as_deref().unwrap_or("no solution")
+                ));
+            }
+        }
+        rows.push(row);
+    }
+
+    let num_cols = rows[0].len();
+    let widths: Vec<usize> = (0..num_cols)
+        .map(|col| {
+            rows.iter()
+                .filter_map(|row| row.get(col))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let mut table = String::new();
+    for row in rows {
+        let cells: Vec<String> = row
+            .iter()
+            .enumerate()
+            .map(|(col, cell)| format!("{cell:>width$}", width = widths[col]))
+            .collect();
+        table.push_str(cells.join("  ").trim_end());
+        table.push('\n');
+    }
+    for note in notes {
+        table.push('\n');
+        table.push_str(&note);
+        table.push('\n');
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{Answer, PartResult, Solver};
+
+    struct Panicker;
+
+    impl Solver for Panicker {
+        type Input = String;
+
+        fn parse(&self, contents: String) -> String {
+            contents
+        }
+        fn part_1(&self, _contents: &String) -> Answer {
+            panic!("out of cheese")
+        }
+        fn part_2(&self, _contents: &String) -> Answer {
+            Answer::Unsolved
+        }
+    }
+
+    fn mock_registry() -> Registry {
+        let mut registry = Registry::default();
+        registry.register(2022, 1, Panicker);
+        registry
+    }
+
+    fn mock_report(day: i32, part_1: Answer) -> DayReport {
+        DayReport {
+            year: 2022,
+            day,
+            input: format!("inputs/2022.{day}"),
+            solution: Some(Solution {
+                parse_time: Duration::from_micros(5),
+                parts: vec![
+                    PartResult {
+                        part: Part::One,
+                        answer: part_1,
+                        elapsed: Duration::from_millis(12),
+                    },
+                    PartResult {
+                        part: Part::Two,
+                        answer: Answer::Unsolved,
+                        elapsed: Duration::from_secs(3),
+                    },
+                ],
+            }),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_run_day() {
+        let registry = mock_registry();
+        // any file will do for an input
+        let report = run_day(&registry, 2022, 1, "Cargo.toml", &Part::BOTH);
+        assert!(report.failed());
+        assert_eq!(report.error, Some("panicked: out of cheese".to_string()));
+
+        let report = run_day(&registry, 2022, 1, "Cargo.toml", &[Part::Two]);
+        assert!(!report.failed());
+        assert_eq!(report.solution.unwrap().parts[0].answer, Answer::Unsolved);
+
+        let report = run_day(&registry, 2022, 1, "no/such/file", &Part::BOTH);
+        assert_eq!(
+            report.error.unwrap(),
+            "No input found for 2022 day 1: no/such/file does not exist."
+        );
+        let report = run_day(&registry, 2022, 2, "Cargo.toml", &Part::BOTH);
+        assert!(report.failed());
+    }
+
+    #[test]
+    fn test_read_input() {
+        assert_eq!(input_path(2022, 5, None), "inputs/2022.5");
+        assert_eq!(input_path(2022, 5, Some("test")), "inputs/2022.5.test");
+        assert!(read_input(2022, 1, "Cargo.toml").is_ok());
+
+        let e = read_input(2031, 1, &input_path(2031, 1, None)).unwrap_err();
+        assert!(e.starts_with("No input found for 2031 day 1: inputs/2031.1 does not exist."));
+        assert!(e.contains("cargo run fetch 2031 1"));
+        let e = read_input(2031, 1, &input_path(2031, 1, Some("test"))).unwrap_err();
+        assert!(e.contains("example"));
+        // a directory exists, but isn't readable as a file
+        assert!(read_input(2031, 1, "src")
+            .unwrap_err()
+            .starts_with("Could not read src"));
+    }
+
+    #[test]
+    fn test_to_json() {
+        let report = mock_report(1, Answer::Number(24000));
+        assert_eq!(
+            report.to_json().to_string(),
+            r#"{"day":1,"error":null,"input":"inputs/2022.1","parse_us":5,"parts":[{"answer":24000,"part":1,"time_us":12000},{"answer":null,"part":2,"time_us":3000000}],"year":2022}"#
+        );
+        let registry = mock_registry();
+        let report = run_day(&registry, 2022, 1, "Cargo.toml", &Part::BOTH);
+        let json = report.to_json();
+        assert_eq!(json["error"], "panicked: out of cheese");
+        assert_eq!(json["parts"], json!([]));
+        assert!(json["parse_us"].is_null());
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_micros(15)), "15 µs");
+        assert_eq!(format_duration(Duration::from_micros(1_500)), "1.50 ms");
+        assert_eq!(format_duration(Duration::from_millis(2_500)), "2.50 s");
+    }
+
+    #[test]
+    fn test_summary_table() {
+        let mut failed = mock_report(3, Answer::Unsolved);
+        failed.solution = None;
+        failed.error = Some("panicked: oops".to_string());
+        let reports = vec![
+            mock_report(1, Answer::Number(24000)),
+            mock_report(10, Answer::Text("\nab\ncd".to_string())),
+            failed,
+        ];
+        let table = summary_table(&reports);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(
+            lines[0],
+            "Year  Day   Parse       Part 1      Time    Part 2    Time"
+        );
+        assert_eq!(
+            lines[1],
+            "2022    1    5 µs        24000  12.00 ms  unsolved  3.00 s"
+        );
+        assert_eq!(lines[3], "2022    3  FAILED");
+        assert!(table.contains("2022 day 10 part 1:\nab\ncd"));
+        assert!(table.contains("2022 day 3: panicked: oops"));
+    }
+}
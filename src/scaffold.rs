@@ -0,0 +1,118 @@
+/*
+Create the files for a new day: `src/yYYYY/day_N.rs` from `src/day_template.rs`,
+`src/yYYYY.rs` if it's the first day of a new year, and an empty
+`inputs/YYYY.N.test` to paste the example into.
+
+Nothing needs to be registered by hand; build.rs picks up the new files.
+*/
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const DAY_TEMPLATE: &str = include_str!("day_template.rs");
+
+fn year_module(year: i32) -> String {
+    format!(
+        "// Solutions for Advent of Code {year}
+use crate::solver::Registry;
+
+pub const YEAR: i32 = {year};
+
+// `pub mod day_N;` for each file in `src/y{year}/`, and the `register()` function
+// that adds them to the registry, are generated by build.rs
+include!(concat!(env!(\"OUT_DIR\"), \"/y{year}.rs\"));
+"
+    )
+}
+
+// Write a file only if it isn't there yet; returns whether it was written
+fn create(path: &Path, contents: &str) -> Result<bool, String> {
+    if path.exists() {
+        return Ok(false);
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Could not create {}: {e}", dir.display()))?;
+    }
+    fs::write(path, contents).map_err(|e| format!("Could not write {}: {e}", path.display()))?;
+    Ok(true)
+}
+
+pub fn new_day(root: &Path, year: i32, day: i32) -> Result<Vec<PathBuf>, String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("Day must be between 1 and 25, not {day}"));
+    }
+    let day_path = root.join(format!("src/y{year}/day_{day}.rs"));
+    if day_path.exists() {
+        return Err(format!("{} already exists", day_path.display()));
+    }
+
+    let mut created = Vec::new();
+    let year_path = root.join(format!("src/y{year}.rs"));
+    if create(&year_path, &year_module(year))? {
+        created.push(year_path);
+    }
+    create(
+        &day_path,
+        &DAY_TEMPLATE.replace("DayX", &format!("Day{day}")),
+    )?;
+    created.push(day_path);
+    let test_path = root.join(format!("inputs/{year}.{day}.test"));
+    if create(&test_path, "")? {
+        created.push(test_path);
+    }
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetch::tests::temp_dir;
+
+    #[test]
+    fn test_new_day() {
+        let root = temp_dir("scaffold");
+        let created = new_day(&root, 2023, 1).unwrap();
+        assert_eq!(
+            created,
+            vec![
+                root.join("src/y2023.rs"),
+                root.join("src/y2023/day_1.rs"),
+                root.join("inputs/2023.1.test")
+            ]
+        );
+        let source = fs::read_to_string(root.join("src/y2023/day_1.rs")).unwrap();
+        assert!(source.contains("pub struct Day1;"));
+        assert!(source.contains("impl Solver for Day1"));
+        let year = fs::read_to_string(root.join("src/y2023.rs")).unwrap();
+        assert!(year.contains("pub const YEAR: i32 = 2023;"));
+        assert!(year.contains("\"/y2023.rs\""));
+
+        // the year module is only written once, and a day is never overwritten
+        assert_eq!(
+            new_day(&root, 2023, 2).unwrap(),
+            vec![
+                root.join("src/y2023/day_2.rs"),
+                root.join("inputs/2023.2.test")
+            ]
+        );
+        assert!(new_day(&root, 2023, 1)
+            .unwrap_err()
+            .ends_with("already exists"));
+        assert!(new_day(&root, 2023, 26).is_err());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_year_module() {
+        // a new year looks just like the existing one
+        assert_eq!(
+            year_module(2022),
+            include_str!("y2022.rs")
+                .split("#[cfg(test)]")
+                .next()
+                .unwrap()
+                .trim_end()
+                .to_string()
+                + "\n"
+        );
+    }
+}
@@ -0,0 +1,311 @@
+/*
+Common interface for every day's puzzle.
+
+A day implements `Solver`: `parse` turns the raw puzzle input into whatever
+structure both parts need, and `part_1` / `part_2` compute the answers from it.
+The `Registry` maps (year, day) to a solver so the runner can look days up
+instead of going through a hand-written match statement.
+*/
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+    Unsolved,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{n}"),
+            Answer::Text(s) => write!(f, "{s}"),
+            Answer::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+// Numbers stay numbers in JSON output, and an unsolved part is `null`
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Answer::Number(n) => serializer.serialize_i64(*n),
+            Answer::Text(s) => serializer.serialize_str(s),
+            Answer::Unsolved => serializer.serialize_none(),
+        }
+    }
+}
+
+// Anything too big for an i64 is kept as its digits rather than wrapping
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Answer {
+                    match i64::try_from(n) {
+                        Ok(n) => Answer::Number(n),
+                        Err(_) => Answer::Text(n.to_string()),
+                    }
+                }
+            }
+        )*
+    };
+}
+answer_from_int!(i32, u32, i64, u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        Answer::Text(s.to_string())
+    }
+}
+
+// Stored as a plain 1 or 2 in answer files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+impl TryFrom<u8> for Part {
+    type Error = &'static str;
+
+    fn try_from(n: u8) -> Result<Part, Self::Error> {
+        n.to_string().parse()
+    }
+}
+
+impl From<Part> for u8 {
+    fn from(part: Part) -> u8 {
+        match part {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Part, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err("Part must be 1 or 2"),
+        }
+    }
+}
+
+pub trait Solver {
+    type Input;
+
+    fn parse(&self, contents: String) -> Self::Input;
+    fn part_1(&self, input: &Self::Input) -> Answer;
+    fn part_2(&self, input: &Self::Input) -> Answer;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartResult {
+    pub part: Part,
+    pub answer: Answer,
+    pub elapsed: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solution {
+    pub parse_time: Duration,
+    pub parts: Vec<PartResult>,
+}
+
+// Object-safe wrapper around `Solver` so that days with different
+// `Input` types can live in the same registry.
+pub trait DynSolver {
+    fn solve(&self, contents: String, parts: &[Part]) -> Solution;
+}
+
+impl<S: Solver> DynSolver for S {
+    fn solve(&self, contents: String, parts: &[Part]) -> Solution {
+        let start = Instant::now();
+        let input = self.parse(contents);
+        let parse_time = start.elapsed();
+        let parts = parts
+            .iter()
+            .map(|part| {
+                let start = Instant::now();
+                let answer = match part {
+                    Part::One => self.part_1(&input),
+                    Part::Two => self.part_2(&input),
+                };
+                PartResult {
+                    part: *part,
+                    answer,
+                    elapsed: start.elapsed(),
+                }
+            })
+            .collect();
+        Solution { parse_time, parts }
+    }
+}
+
+#[derive(Default)]
+pub struct Registry {
+    solvers: BTreeMap<(i32, i32), Box<dyn DynSolver>>,
+}
+
+impl Registry {
+    pub fn register(&mut self, year: i32, day: i32, solver: impl DynSolver + 'static) {
+        if self.solvers.insert((year, day), Box::new(solver)).is_some() {
+            panic!("{year} day {day} registered twice!");
+        }
+    }
+
+    pub fn get(&self, year: i32, day: i32) -> Option<&dyn DynSolver> {
+        self.solvers.get(&(year, day)).map(|s| s.as_ref())
+    }
+
+    pub fn find(&self, year: i32, day: i32) -> Result<&dyn DynSolver, String> {
+        // Same as `get`, but explains what's missing
+        match self.get(year, day) {
+            Some(solver) => Ok(solver),
+            None if !self.years().contains(&year) => {
+                Err(format!("Puzzles are not implemented for year {year}"))
+            }
+            None => Err(format!("Day {day} is not implemented for year {year}")),
+        }
+    }
+
+    pub fn years(&self) -> Vec<i32> {
+        let mut years: Vec<i32> = self.list().map(|(year, _)| year).collect();
+        years.dedup();
+        years
+    }
+
+    pub fn list(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        // sorted by year, then day
+        self.solvers.keys().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Doubler;
+
+    impl Solver for Doubler {
+        type Input = Vec<i32>;
+
+        fn parse(&self, contents: String) -> Vec<i32> {
+            contents.lines().map(|l| l.parse().unwrap()).collect()
+        }
+        fn part_1(&self, input: &Vec<i32>) -> Answer {
+            input.iter().map(|n| n * 2).sum::<i32>().into()
+        }
+        fn part_2(&self, _input: &Vec<i32>) -> Answer {
+            Answer::Unsolved
+        }
+    }
+
+    fn answers(solution: Solution) -> Vec<(Part, Answer)> {
+        solution
+            .parts
+            .into_iter()
+            .map(|p| (p.part, p.answer))
+            .collect()
+    }
+
+    #[test]
+    fn test_solve() {
+        let answers = answers(Doubler.solve("1\n2\n3\n".to_string(), &Part::BOTH));
+        assert_eq!(
+            answers,
+            vec![
+                (Part::One, Answer::Number(12)),
+                (Part::Two, Answer::Unsolved)
+            ]
+        );
+        let solution = Doubler.solve("4\n".to_string(), &[Part::Two]);
+        assert_eq!(solution.parts.len(), 1);
+        assert_eq!(solution.parts[0].part, Part::Two);
+    }
+
+    #[test]
+    fn test_registry() {
+        let mut registry = Registry::default();
+        registry.register(2022, 2, Doubler);
+        registry.register(2021, 7, Doubler);
+        registry.register(2022, 1, Doubler);
+        assert_eq!(
+            registry.list().collect::<Vec<_>>(),
+            vec![(2021, 7), (2022, 1), (2022, 2)]
+        );
+        assert!(registry.get(2022, 1).is_some());
+        assert!(registry.get(2023, 1).is_none());
+        assert_eq!(registry.years(), vec![2021, 2022]);
+    }
+
+    #[test]
+    fn test_find() {
+        let mut registry = Registry::default();
+        registry.register(2022, 5, Doubler);
+        assert!(registry.find(2022, 5).is_ok());
+        assert_eq!(
+            registry.find(2022, 6).err(),
+            Some("Day 6 is not implemented for year 2022".to_string())
+        );
+        assert_eq!(
+            registry.find(2023, 5).err(),
+            Some("Puzzles are not implemented for year 2023".to_string())
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_register_twice() {
+        let mut registry = Registry::default();
+        registry.register(2022, 1, Doubler);
+        registry.register(2022, 1, Doubler);
+    }
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(Answer::from(42_usize), Answer::Number(42));
+        assert_eq!(
+            Answer::from(u64::MAX),
+            Answer::Text("18446744073709551615".to_string())
+        );
+        assert_eq!(Answer::from("CMZ").to_string(), "CMZ");
+        assert_eq!(Answer::from(-3_i32).to_string(), "-3");
+        assert_eq!("2".parse::<Part>(), Ok(Part::Two));
+        assert!("3".parse::<Part>().is_err());
+        assert_eq!(serde_json::to_string(&Answer::Number(7)).unwrap(), "7");
+        assert_eq!(
+            serde_json::to_string(&Answer::from("CMZ")).unwrap(),
+            "\"CMZ\""
+        );
+        assert_eq!(serde_json::to_string(&Answer::Unsolved).unwrap(), "null");
+    }
+}
@@ -0,0 +1,282 @@
+/*
+Submit an answer to adventofcode.com, and keep track of how it went.
+
+Correct answers and rejected guesses are saved in the year's answer file, and
+an answer that's already known to be wrong is never sent. When the site asks
+us to wait before trying again, the time we can try again is saved in
+`answers/.submit_wait` so nothing is sent before then.
+*/
+use crate::answers::{AnswerBook, Hint, Rejected, Verdict};
+use crate::fetch::Fetcher;
+use crate::puzzle;
+use crate::solver::{Answer, Part};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const WAIT_FILE: &str = "answers/.submit_wait";
+
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Correct,
+    Wrong(Hint),
+    TooSoon(u64),
+    AlreadySolved,
+    Unrecognized(String),
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "That's the right answer!"),
+            Outcome::Wrong(Hint::Incorrect) => write!(f, "That's not the right answer"),
+            Outcome::Wrong(hint) => write!(f, "That's not the right answer: {hint}"),
+            Outcome::TooSoon(secs) => write!(f, "Answered too recently, wait {secs}s"),
+            Outcome::AlreadySolved => write!(f, "This part has already been solved"),
+            Outcome::Unrecognized(text) => write!(f, "Unrecognized response: {text}"),
+        }
+    }
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+// How long the site wants us to wait, from phrases like
+// "You have 1m 23s left to wait" or "please wait one minute before trying again"
+fn wait_seconds(text: &str) -> Option<u64> {
+    if let Some((before, _)) = text.split_once(" left to wait") {
+        let amount = before.rsplit("You have ").next()?;
+        return amount
+            .split_whitespace()
+            .map(
+                |chunk| match (chunk.strip_suffix('m'), chunk.strip_suffix('s')) {
+                    (Some(mins), _) => mins.parse::<u64>().ok().map(|n| n * 60),
+                    (_, Some(secs)) => secs.parse::<u64>().ok(),
+                    _ => None,
+                },
+            )
+            .sum();
+    }
+    let (_, after) = text.split_once("wait ")?;
+    let mut words = after.split_whitespace();
+    let count = match words.next()? {
+        "one" | "a" => 1,
+        n => n.parse::<u64>().ok()?,
+    };
+    match words.next()? {
+        unit if unit.starts_with("minute") => Some(count * 60),
+        unit if unit.starts_with("second") => Some(count),
+        _ => None,
+    }
+}
+
+pub fn parse_response(html: &str) -> (Outcome, Option<u64>) {
+    // the message is in the page's only <article>
+    let text = match html.split_once("<article>") {
+        Some((_, rest)) => puzzle::clean(rest.split("</article>").next().unwrap_or(rest)),
+        None => puzzle::clean(html),
+    };
+    let text = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+    let wait = wait_seconds(&text);
+    let outcome = if text.contains("That's the right answer") {
+        Outcome::Correct
+    } else if text.contains("That's not the right answer") {
+        Outcome::Wrong(if text.contains("too high") {
+            Hint::TooHigh
+        } else if text.contains("too low") {
+            Hint::TooLow
+        } else {
+            Hint::Incorrect
+        })
+    } else if text.contains("You gave an answer too recently") {
+        Outcome::TooSoon(wait.unwrap_or(60))
+    } else if text.contains("You don't seem to be solving the right level") {
+        Outcome::AlreadySolved
+    } else {
+        Outcome::Unrecognized(text)
+    };
+    (outcome, wait)
+}
+
+fn read_wait(wait_file: &Path) -> u64 {
+    fs::read_to_string(wait_file)
+        .ok()
+        .and_then(|s| s.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+pub fn submit(
+    fetcher: &Fetcher,
+    book: &mut AnswerBook,
+    wait_file: &Path,
+    (year, day, part): (i32, i32, Part),
+    answer: &Answer,
+) -> Result<Outcome, String> {
+    if *answer == Answer::Unsolved {
+        return Err(format!("Nothing to submit, part {part} is unsolved"));
+    }
+    if let Some(correct) = book.get(day, part).and_then(|r| r.correct.as_ref()) {
+        return Err(format!("Part {part} was already solved with {correct}"));
+    }
+    if let Verdict::Fail(why) = book.check(day, part, answer) {
+        return Err(format!("Not submitting {answer}: {why}"));
+    }
+    let wait_until = read_wait(wait_file);
+    if now() < wait_until {
+        return Err(format!(
+            "Answered too recently, wait another {}s",
+            wait_until - now()
+        ));
+    }
+
+    let level = u8::from(part).to_string();
+    let answer_text = answer.to_string();
+    let html = fetcher.post(
+        &format!("/{year}/day/{day}/answer"),
+        &[("level", &level), ("answer", &answer_text)],
+    )?;
+    let (outcome, wait) = parse_response(&html);
+    if let Some(wait) = wait {
+        fs::write(wait_file, (now() + wait).to_string())
+            .map_err(|e| format!("Could not write {}: {e}", wait_file.display()))?;
+    }
+    let record = book.get_mut(day, part);
+    match outcome {
+        Outcome::Correct => record.correct = Some(answer_text),
+        Outcome::Wrong(hint) => record.rejected.push(Rejected {
+            answer: answer_text,
+            hint,
+        }),
+        _ => (),
+    }
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetch::tests::{mock_server, temp_dir};
+
+    fn article(text: &str) -> String {
+        format!("<html><main>\n<article><p>{text}</p></article>\n</main></html>")
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let html = article("That's the right answer!  You are <em>one gold star</em> closer.");
+        assert_eq!(parse_response(&html), (Outcome::Correct, None));
+        let html = article(
+            "That's not the right answer; your answer is too high. \
+             Please wait one minute before trying again. [<a href=\"/2022/day/13\">Return</a>]",
+        );
+        assert_eq!(
+            parse_response(&html),
+            (Outcome::Wrong(Hint::TooHigh), Some(60))
+        );
+        let html =
+            article("That's not the right answer. Please wait 5 minutes before trying again.");
+        assert_eq!(
+            parse_response(&html),
+            (Outcome::Wrong(Hint::Incorrect), Some(300))
+        );
+        let html = article("You gave an answer too recently. You have 1m 23s left to wait.");
+        assert_eq!(parse_response(&html), (Outcome::TooSoon(83), Some(83)));
+        let html = article("You gave an answer too recently. You have 38s left to wait.");
+        assert_eq!(parse_response(&html).0, Outcome::TooSoon(38));
+        let html =
+            article("You don't seem to be solving the right level.  Did you already complete it?");
+        assert_eq!(parse_response(&html).0, Outcome::AlreadySolved);
+        let html = article("Something <em>new</em>");
+        assert_eq!(
+            parse_response(&html).0,
+            Outcome::Unrecognized("Something new".to_string())
+        );
+    }
+
+    #[test]
+    fn test_submit() {
+        let (base_url, server) = mock_server(vec![
+            (200, article("That's not the right answer; your answer is too low. Please wait one minute before trying again.")),
+            (200, article("That's the right answer!")),
+        ]);
+        let dir = temp_dir("submit");
+        fs::create_dir_all(&dir).unwrap();
+        let wait_file = dir.join(".submit_wait");
+        let fetcher = Fetcher::new(&base_url, "abc123");
+        let mut book = AnswerBook::default();
+        let puzzle = (2022, 13, Part::One);
+
+        let outcome = submit(
+            &fetcher,
+            &mut book,
+            &wait_file,
+            puzzle,
+            &Answer::Number(4781),
+        );
+        assert_eq!(outcome, Ok(Outcome::Wrong(Hint::TooLow)));
+        let record = book.get(13, Part::One).unwrap();
+        assert_eq!(record.rejected[0].answer, "4781");
+        // known-bad answers, and anything during the wait, never get sent
+        let e = submit(
+            &fetcher,
+            &mut book,
+            &wait_file,
+            puzzle,
+            &Answer::Number(4781),
+        );
+        assert_eq!(
+            e,
+            Err("Not submitting 4781: already rejected as too low".to_string())
+        );
+        let e = submit(
+            &fetcher,
+            &mut book,
+            &wait_file,
+            puzzle,
+            &Answer::Number(100),
+        );
+        assert!(e.unwrap_err().contains("which was too low"));
+        let e = submit(
+            &fetcher,
+            &mut book,
+            &wait_file,
+            puzzle,
+            &Answer::Number(5000),
+        );
+        assert!(e.unwrap_err().starts_with("Answered too recently"));
+
+        fs::write(&wait_file, "0").unwrap();
+        let outcome = submit(
+            &fetcher,
+            &mut book,
+            &wait_file,
+            puzzle,
+            &Answer::Number(5000),
+        );
+        assert_eq!(outcome, Ok(Outcome::Correct));
+        assert_eq!(
+            book.get(13, Part::One).unwrap().correct,
+            Some("5000".to_string())
+        );
+        let e = submit(
+            &fetcher,
+            &mut book,
+            &wait_file,
+            puzzle,
+            &Answer::Number(5000),
+        );
+        assert!(e.unwrap_err().contains("already solved"));
+        assert!(submit(&fetcher, &mut book, &wait_file, puzzle, &Answer::Unsolved).is_err());
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2022/day/13/answer HTTP/1.1"));
+        assert!(requests[0].ends_with("level=1&answer=4781"));
+        assert!(requests[1].ends_with("level=1&answer=5000"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
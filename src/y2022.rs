@@ -0,0 +1,22 @@
+// Solutions for Advent of Code 2022
+use crate::solver::Registry;
+
+pub const YEAR: i32 = 2022;
+
+// `pub mod day_N;` for each file in `src/y2022/`, and the `register()` function
+// that adds them to the registry, are generated by build.rs
+include!(concat!(env!("OUT_DIR"), "/y2022.rs"));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register() {
+        let mut registry = Registry::default();
+        register(&mut registry);
+        assert_eq!(registry.list().count(), 19);
+        assert!(registry.list().all(|(year, _)| year == YEAR));
+        assert!(registry.get(YEAR, 17).is_none());
+    }
+}
@@ -3,7 +3,29 @@ Text input will be groups of numbers separated by newlines
 Goal is to find elf with most calories, i.e. largest total between any two blank lines.
 */
 
-pub fn main(contents: String) {
+use crate::solver::{Answer, Solver};
+
+pub struct Day1;
+
+impl Solver for Day1 {
+    type Input = Vec<i32>;
+
+    fn parse(&self, contents: String) -> Vec<i32> {
+        elf_totals(contents)
+    }
+
+    fn part_1(&self, elf_cal_counts: &Vec<i32>) -> Answer {
+        elf_cal_counts[0].into()
+    }
+
+    fn part_2(&self, elf_cal_counts: &Vec<i32>) -> Answer {
+        let top_3: i32 = elf_cal_counts[0..3].iter().sum();
+        top_3.into()
+    }
+}
+
+fn elf_totals(contents: String) -> Vec<i32> {
+    // total calories carried by each elf, largest first
     let mut current_elf = 0;
     let mut elf_cal_counts: Vec<i32> = Vec::new();
 
@@ -18,10 +40,7 @@ pub fn main(contents: String) {
     }
     elf_cal_counts.sort();
     elf_cal_counts.reverse();
-    let top_3: i32 = elf_cal_counts[0..3].iter().sum();
-
-    println!("Part 1: {:?}", elf_cal_counts[0]);
-    println!("Part 2: {top_3}");
+    elf_cal_counts
 }
 
 fn content_to_ints(contents: String) -> Vec<i32> {
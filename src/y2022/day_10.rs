@@ -0,0 +1,207 @@
+use crate::common::animate::{self, Recorder};
+use crate::common::cpu::{self, Cpu, Op, Registers};
+use crate::common::ocr;
+use crate::common::render::{self, Image};
+use crate::common::Grid;
+use crate::solver::{Answer, Solver};
+
+const OFF: char = '🎄';
+const ON: char = '🎅';
+const LINE_LEN: usize = 40;
+
+pub struct Day10;
+
+impl Solver for Day10 {
+    type Input = Vec<Op>;
+
+    fn parse(&self, contents: String) -> Vec<Op> {
+        cpu::parse_program(&contents).unwrap_or_else(|e| panic!("bad program: {e}"))
+    }
+
+    fn part_1(&self, program: &Vec<Op>) -> Answer {
+        let mut part_1 = 0;
+        run(program, |cycle, registers| {
+            // cycles 20, 60, 100, ...
+            if cycle % LINE_LEN == 20 {
+                part_1 += signal_strength(cycle, registers);
+            }
+        });
+        part_1.into()
+    }
+
+    fn part_2(&self, program: &Vec<Op>) -> Answer {
+        let cycles = &process_signal(program);
+        let num_pixels = cycles.len() - 1;
+        render::save("2022.10.png", || crt_image(cycles, num_pixels).scaled(8));
+        let mut frames = Recorder::from_env();
+        for drawn in 0..=num_pixels {
+            frames.record(|| crt_image(cycles, drawn));
+        }
+        animate::save("2022.10", frames.frames(), 8);
+        read_screen(cycles)
+    }
+}
+
+// The letters on the screen, or the picture if they can't be read (like the
+// example, which isn't letters)
+fn read_screen(cycles: &[i32]) -> Answer {
+    match ocr::read(&screen(cycles)) {
+        Some(letters) => letters.into(),
+        None => render_screen(cycles).into(),
+    }
+}
+
+fn render_screen(cycles: &[i32]) -> String {
+    let mut screen = String::new();
+    for i in 0..cycles.len() - 1 {
+        if i % LINE_LEN == 0 {
+            screen.push('\n');
+        }
+        match render_pixel(cycles, i) {
+            true => screen.push(ON),
+            false => screen.push(OFF),
+        }
+    }
+    screen
+}
+// the lit pixels, one row per line of the CRT
+fn screen(cycles: &[i32]) -> Grid<bool> {
+    let num_pixels = cycles.len() - 1;
+    let mut screen = Grid::new(num_pixels / LINE_LEN, LINE_LEN, false);
+    for (i, pixel) in screen.data.iter_mut().enumerate() {
+        *pixel = render_pixel(cycles, i);
+    }
+    screen
+}
+
+// The screen after `drawn` pixels, with the sprite's position on the row
+// being drawn shown in green
+fn crt_image(cycles: &[i32], drawn: usize) -> Image {
+    let screen = screen(cycles);
+    let mut image = Image::from_grid(&screen, |lit| match lit {
+        true => render::GOLD,
+        false => render::BLACK,
+    });
+    let sprite = cycles[drawn];
+    for (index, pixel) in image.pixels.iter_mut().enumerate().skip(drawn) {
+        let on_sprite = (sprite - (index % LINE_LEN) as i32).abs() <= 1;
+        *pixel = match index / LINE_LEN == drawn / LINE_LEN && on_sprite {
+            true => render::GREEN,
+            false => render::BLACK,
+        };
+    }
+    image
+}
+
+fn render_pixel(cycles: &[i32], index: usize) -> bool {
+    let row_index: usize = index % LINE_LEN;
+    (cycles[index] - row_index as i32).abs() <= 1
+}
+
+// x starts at 1, and `hook` sees it during every cycle
+fn run(program: &[Op], hook: impl FnMut(usize, &Registers)) -> Cpu<Op> {
+    let mut cpu = Cpu::new(program.to_vec()).with_register('x', 1);
+    cpu.run(hook);
+    cpu
+}
+
+// The sprite's position during each cycle, plus where it ends up
+fn process_signal(program: &[Op]) -> Vec<i32> {
+    let mut cycles: Vec<i32> = Vec::new();
+    let cpu = run(program, |_, registers| cycles.push(registers['x'] as i32));
+    cycles.push(cpu.registers['x'] as i32);
+    cycles
+}
+
+fn signal_strength(cycle: usize, registers: &Registers) -> i64 {
+    cycle as i64 * registers['x']
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn example() -> Vec<Op> {
+        Day10.parse(fs::read_to_string("inputs/2022.10.test").unwrap())
+    }
+
+    #[test]
+    fn test_render() {
+        let cycles = process_signal(&example());
+        // lines are 40 chars long
+        assert!(render_pixel(&cycles, 0));
+        assert!(!render_pixel(&cycles, 2));
+        assert!(!render_pixel(&cycles, 6));
+        assert!(render_pixel(&cycles, 12));
+        assert!(render_pixel(&cycles, 40));
+        assert!(!render_pixel(&cycles, 43));
+        let screen = screen(&cycles);
+        assert_eq!((screen.num_rows, screen.num_cols), (6, 40));
+        assert!(screen.get(1, 0).unwrap());
+        assert!(!screen.get(1, 3).unwrap());
+
+        let image = crt_image(&cycles, 240);
+        assert_eq!(image.get(0, 0), Some(render::GOLD));
+        assert_eq!(image.get(2, 0), Some(render::BLACK));
+        // after 4 pixels, the sprite covers the next 3, which aren't drawn yet
+        let image = crt_image(&cycles, 4);
+        assert_eq!(image.get(0, 0), Some(render::GOLD));
+        assert_eq!(image.get(4, 0), Some(render::GREEN));
+        assert_eq!(image.get(6, 0), Some(render::GREEN));
+        assert_eq!(image.get(7, 0), Some(render::BLACK));
+        assert_eq!(image.get(0, 1), Some(render::BLACK));
+    }
+
+    #[test]
+    fn test_letters() {
+        // "EYH", with the Y running into the H like it does on the CRT. The
+        // sprite sits on each pixel that should be lit while it's drawn, and
+        // off the screen otherwise.
+        let rows = [
+            "####.#...##..#",
+            "#....#...##..#",
+            "###...#.#.####",
+            "#......#..#..#",
+            "#......#..#..#",
+            "####...#..#..#",
+        ];
+        let mut cycles: Vec<i32> = rows
+            .iter()
+            .flat_map(|row| format!("{row:.<40}").into_bytes().into_iter().enumerate())
+            .map(|(col, pixel)| if pixel == b'#' { col as i32 } else { -5 })
+            .collect();
+        cycles.push(1);
+        assert_eq!(read_screen(&cycles), Answer::from("EYH"));
+        let example = process_signal(&example());
+        assert_eq!(read_screen(&example), Answer::from(render_screen(&example)));
+    }
+
+    #[test]
+    fn test_sample() {
+        let cycles = process_signal(&example());
+        assert_eq!(cycles[19], 21);
+        assert_eq!(cycles[59], 19);
+        assert_eq!(cycles[99], 18);
+        assert_eq!(cycles[139], 21);
+        assert_eq!(cycles[179], 16);
+        assert_eq!(cycles[219], 18);
+    }
+
+    #[test]
+    fn test_strength() {
+        let mut strengths = Vec::new();
+        run(&example(), |cycle, registers| {
+            if cycle % LINE_LEN == 20 {
+                strengths.push(signal_strength(cycle, registers));
+            }
+        });
+        assert_eq!(strengths, vec![420, 1140, 1800, 2940, 2880, 3960]);
+        assert_eq!(Day10.part_1(&example()), Answer::from(13140));
+    }
+    #[test]
+    fn test_process() {
+        let program = vec![Op::Noop, Op::Addx(3), Op::Addx(-5)];
+        assert_eq!(process_signal(&program), vec![1, 1, 1, 4, 4, -1]);
+    }
+}
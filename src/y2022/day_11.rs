@@ -1,12 +1,32 @@
+use crate::solver::{Answer, Solver};
 use std::collections::HashMap;
 
-pub fn main(contents: String) {
-    let mut monkeys = parse_monkeys(contents);
-    for _ in 0..10000 {
-        take_turn(&mut monkeys);
+pub struct Day11;
+
+impl Solver for Day11 {
+    // monkey operations are boxed closures and can't be cloned,
+    // so each part parses its own set of monkeys
+    type Input = String;
+
+    fn parse(&self, contents: String) -> String {
+        contents
+    }
+
+    fn part_1(&self, contents: &String) -> Answer {
+        let mut monkeys = parse_monkeys(contents.clone());
+        for _ in 0..20 {
+            take_turn(&mut monkeys, true);
+        }
+        monkey_business(&monkeys).into()
+    }
+
+    fn part_2(&self, contents: &String) -> Answer {
+        let mut monkeys = parse_monkeys(contents.clone());
+        for _ in 0..10000 {
+            take_turn(&mut monkeys, false);
+        }
+        monkey_business(&monkeys).into()
     }
-    let mb = monkey_business(&monkeys);
-    println!("Quantity of Monkey Business: {mb}");
 }
 
 struct Monkey {
@@ -18,7 +38,7 @@ struct Monkey {
     num_inspections: u64,
 }
 
-fn take_turn(monkeys: &mut HashMap<usize, Monkey>) {
+fn take_turn(monkeys: &mut HashMap<usize, Monkey>, relief: bool) {
     let monkey_test_mod: u32 = monkeys
         .iter()
         .map(|(_, m)| m.test)
@@ -32,10 +52,12 @@ fn take_turn(monkeys: &mut HashMap<usize, Monkey>) {
         let false_index = current_monkey.test_false;
         while let Some(mut item) = current_monkey.items.pop() {
             current_monkey.num_inspections += 1; // inspect item
-                                                 // item = item % current_monkey.test as u64;
             item = (current_monkey.operation)(item);
-            // item /= 3; // decrease worry
-            item %= monkey_test_mod as u64; // decrease worry
+            if relief {
+                item /= 3; // decrease worry
+            } else {
+                item %= monkey_test_mod as u64; // keep worry from overflowing
+            }
             match item % current_monkey.test as u64 {
                 0 => thrown_true.push(item),
                 _ => thrown_false.push(item),
@@ -161,7 +183,7 @@ mod tests {
             vec![54, 65, 75, 74]
         );
         assert_eq!(parse_items("  Starting Items: 74\n"), vec![74]);
-        assert_eq!(parse_items("  Starting Items:\n"), vec![]);
+        assert_eq!(parse_items("  Starting Items:\n"), Vec::<u64>::new());
     }
 
     #[test]
@@ -0,0 +1,218 @@
+use crate::common::geom::{Direction, Point2D};
+use crate::common::render::{self, Image, Rgb};
+use crate::common::{search, Grid};
+use crate::solver::{Answer, Solver};
+
+const START: u8 = b'S';
+const END: u8 = b'E';
+
+pub struct Day12;
+
+// x is the column, y the row
+pub type Point = Point2D<i32>;
+
+fn point(grid: &Grid, index: usize) -> Point {
+    let (row, col) = grid.loc(index).unwrap();
+    Point::new(col as i32, row as i32)
+}
+
+// the height at `p`, None if it's off the map
+fn height(grid: &Grid, p: Point) -> Option<u8> {
+    if p.x < 0 || p.y < 0 {
+        return None;
+    }
+    grid.checked_get(p.y as usize, p.x as usize).copied()
+}
+
+pub struct Heightmap {
+    grid: Grid,
+    start: Point,
+    end: Point,
+    // fewest steps from each cell to the end, None if it can't get there
+    to_end: Grid<Option<usize>>,
+}
+
+impl Heightmap {
+    fn build(mut grid: Grid) -> Heightmap {
+        let start = grid.data.iter().position(|&x| x == START).unwrap();
+        grid.data[start] = b'a';
+        let end = grid.data.iter().position(|&x| x == END).unwrap();
+        grid.data[end] = b'z';
+        let to_end = distances_to(&grid, point(&grid, end));
+        Heightmap {
+            start: point(&grid, start),
+            end: point(&grid, end),
+            grid,
+            to_end,
+        }
+    }
+
+    pub fn steps_from(&self, p: Point) -> Option<usize> {
+        height(&self.grid, p)?;
+        self.to_end[(p.y as usize, p.x as usize)]
+    }
+
+    pub fn distance_field(&self) -> &Grid<Option<usize>> {
+        &self.to_end
+    }
+
+    // one of the shortest paths from `from` to the end, following the
+    // distance field downhill
+    pub fn path_from(&self, from: Point) -> Option<Vec<Point>> {
+        let mut path = vec![from];
+        let mut steps = self.steps_from(from)?;
+        while steps > 0 {
+            let here = *path.last().unwrap();
+            let next = next_moves(&self.grid, here)
+                .into_iter()
+                .find(|p| self.steps_from(*p) == Some(steps - 1))
+                .unwrap();
+            path.push(next);
+            steps -= 1;
+        }
+        Some(path)
+    }
+
+    // low ground is dark green and the peaks are white, with the path from
+    // `from` to the end drawn over it in red
+    pub fn image(&self, from: Point) -> Image {
+        let mut image = Image::from_grid(&self.grid, |h| height_color(*h));
+        for p in self.path_from(from).unwrap_or_default() {
+            image.set(p.x as usize, p.y as usize, render::RED);
+        }
+        image.scaled(4)
+    }
+}
+
+impl Solver for Day12 {
+    type Input = Heightmap;
+
+    fn parse(&self, contents: String) -> Heightmap {
+        let map = Heightmap::build(Grid::build(contents).expect("heightmap should be a grid"));
+        let grid = &map.grid;
+        eprintln!("Start at {}", map.start);
+        eprintln!("End at {}", map.end);
+        eprintln!("Grid Size: {} X {}", grid.num_rows, grid.num_cols);
+        map
+    }
+
+    fn part_1(&self, map: &Heightmap) -> Answer {
+        render::save("2022.12.png", || map.image(map.start));
+        map.steps_from(map.start).unwrap().into()
+    }
+
+    fn part_2(&self, map: &Heightmap) -> Answer {
+        (0..map.grid.data.len())
+            .map(|i| point(&map.grid, i))
+            .filter(|p| height(&map.grid, *p) == Some(b'a'))
+            .filter_map(|p| map.steps_from(p))
+            .min()
+            .unwrap()
+            .into()
+    }
+}
+
+fn height_color(height: u8) -> Rgb {
+    let [r, g, b] = render::GREEN;
+    let [r2, g2, b2] = render::WHITE;
+    let t = height.saturating_sub(b'a') as u32;
+    let blend = |from: u8, to: u8| ((from as u32 * (25 - t) + to as u32 * t) / 25) as u8;
+    [blend(r, r2), blend(g, g2), blend(b, b2)]
+}
+
+// Walk downhill from the end with the climbing rule turned around, so one
+// search gives the distance to the end from every cell at once
+fn distances_to(grid: &Grid, end: Point) -> Grid<Option<usize>> {
+    let search = search::bfs([end], |p| prev_moves(grid, *p), |_| false);
+    let mut field = Grid::new(grid.num_rows, grid.num_cols, None);
+    for (p, &steps) in search.distances() {
+        field[(p.y as usize, p.x as usize)] = Some(steps);
+    }
+    field
+}
+
+// the squares on the map next to `p`
+fn neighbors(grid: &Grid, p: Point) -> impl Iterator<Item = Point> + '_ {
+    Direction::LATERAL
+        .into_iter()
+        .map(move |d| p + d)
+        .filter(|n| height(grid, *n).is_some())
+}
+
+// at most one higher, or any amount lower
+fn next_moves(grid: &Grid, p: Point) -> Vec<Point> {
+    let here = height(grid, p).unwrap() as i32;
+    neighbors(grid, p)
+        .filter(|n| height(grid, *n).unwrap() as i32 - here <= 1)
+        .collect()
+}
+
+// the squares that could have climbed up (or down) to `p`
+fn prev_moves(grid: &Grid, p: Point) -> Vec<Point> {
+    let here = height(grid, p).unwrap() as i32;
+    neighbors(grid, p)
+        .filter(|n| here - height(grid, *n).unwrap() as i32 <= 1)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    fn mock_grid() -> Grid {
+        let mock_input = indoc!(
+            "
+        123
+        456
+        789
+        "
+        )
+        .to_string();
+        Grid::build(mock_input).unwrap()
+    }
+
+    #[test]
+    fn test_next() {
+        let g = mock_grid();
+        let p = Point::new;
+        assert_eq!(next_moves(&g, p(0, 0)), vec![p(1, 0)]);
+        assert_eq!(next_moves(&g, p(1, 1)), vec![p(0, 1), p(2, 1), p(1, 0)]);
+    }
+
+    #[test]
+    fn test_distance_field() {
+        let input = indoc!(
+            "
+        Sabqponm
+        abcryxxl
+        accszExk
+        acctuvwj
+        abdefghi
+        "
+        );
+        let map = Heightmap::build(Grid::build(input.to_string()).unwrap());
+        assert_eq!(map.steps_from(map.start), Some(31));
+        assert_eq!(map.steps_from(map.end), Some(0));
+        // the 'a' just below the start is one step closer than the start,
+        // but the one at the bottom left corner is the closest of all
+        assert_eq!(map.steps_from(Point::new(0, 1)), Some(30));
+        assert_eq!(map.steps_from(Point::new(-1, 0)), None);
+        assert_eq!(map.distance_field().get(4, 0).unwrap(), Some(29));
+        assert_eq!(Day12.part_2(&map), Answer::from(29));
+        assert_eq!(
+            prev_moves(&mock_grid(), Point::new(0, 0)),
+            vec![Point::new(1, 0), Point::new(0, 1)]
+        );
+
+        let path = map.path_from(map.start).unwrap();
+        assert_eq!(path.len(), 32);
+        assert_eq!((path[0], path[31]), (map.start, map.end));
+        assert!(path.windows(2).all(|w| w[0].manhattan(&w[1]) == 1));
+        let image = map.image(map.start);
+        assert_eq!((image.width, image.height), (32, 20));
+        assert_eq!(image.get(0, 0), Some(render::RED));
+        assert_eq!(image.get(5 * 4, 2 * 4), Some(render::RED));
+        assert_eq!(height_color(b'a'), render::GREEN);
+        assert_eq!(height_color(b'z'), render::WHITE);
+    }
+}
@@ -1,19 +1,31 @@
 // 149 pairs
-// answer 4781 too low
-// answer 5629 too high
-pub fn main(contents: String) {
-    let p = parse_pairs(contents);
-    let mut part_1: u32 = 0;
-    for index in 0..p.len() {
-        let pair = p[index].clone();
-        println!("{:?}", &pair);
-        if compare(pair.0, pair.1) {
-            println!("Index: {index}");
-            part_1 += index as u32 + 1
+use crate::solver::{Answer, Solver};
+
+pub struct Day13;
+
+impl Solver for Day13 {
+    type Input = Vec<(String, String)>;
+
+    fn parse(&self, contents: String) -> Vec<(String, String)> {
+        parse_pairs(contents)
+    }
+
+    fn part_1(&self, p: &Vec<(String, String)>) -> Answer {
+        let mut part_1: u32 = 0;
+        for index in 0..p.len() {
+            let pair = p[index].clone();
+            eprintln!("{:?}", &pair);
+            if compare(pair.0, pair.1) {
+                eprintln!("Index: {index}");
+                part_1 += index as u32 + 1
+            }
         }
+        part_1.into()
     }
 
-    println!("Part 1: {part_1}"); // 4781 too low
+    fn part_2(&self, _p: &Vec<(String, String)>) -> Answer {
+        Answer::Unsolved
+    }
 }
 
 fn parse_pairs(contents: String) -> Vec<(String, String)> {
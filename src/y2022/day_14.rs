@@ -0,0 +1,345 @@
+use crate::common::animate::{self, Recorder};
+use crate::common::geom::{Direction, Point2D};
+use crate::common::render::{self, Image, Rgb};
+use crate::common::sparse::OffsetGrid;
+use crate::common::Grid;
+use crate::solver::{Answer, Solver};
+use std::cmp;
+
+const EMPTY: char = ' ';
+const SAND: char = 'o';
+const ROCK: char = '#';
+
+const START: Point = Point { x: 500, y: 0 };
+
+pub struct Day14;
+
+impl Solver for Day14 {
+    type Input = Vec<Path>;
+
+    fn parse(&self, contents: String) -> Vec<Path> {
+        let paths: Vec<Path> = contents.lines().map(parse_path).collect();
+        eprintln!(
+            "Min/Max Coords: {} - {}",
+            coord_min_max(&paths, cmp::min, col),
+            coord_min_max(&paths, cmp::max, col)
+        );
+        paths
+    }
+
+    fn part_1(&self, paths: &Vec<Path>) -> Answer {
+        let mut cave_1 = Cave::build(paths.clone(), false);
+        let mut frames = Recorder::from_env();
+        let part_1 = cave_1.fill(&START, &mut frames);
+        cave_1.display();
+        render::save("2022.14.part1.png", || cave_1.image().scaled(4));
+        animate::save("2022.14.part1", frames.frames(), 4);
+        part_1.into()
+    }
+
+    fn part_2(&self, paths: &Vec<Path>) -> Answer {
+        let mut cave_2 = Cave::build(paths.clone(), true);
+        let mut frames = Recorder::from_env();
+        let part_2 = cave_2.fill(&START, &mut frames);
+        cave_2.display();
+        render::save("2022.14.part2.png", || cave_2.image().scaled(4));
+        animate::save("2022.14.part2", frames.frames(), 2);
+        part_2.into()
+    }
+}
+
+#[derive(PartialEq, Debug)]
+struct Cave {
+    cells: OffsetGrid<CaveCell>,
+    lowest_rock: i32,
+    // an endless floor two below the lowest rock, instead of the abyss
+    floor: bool,
+    // top left & bottom right of the part of the cave that sand can settle in
+    view: (Point, Point),
+}
+
+impl Cave {
+    // with a frame after each unit of sand comes to rest
+    fn fill(&mut self, start: &Point, frames: &mut Recorder<Image>) -> u32 {
+        let mut num_sand = 0;
+        frames.record(|| self.image());
+        while self.drop_sand(start).is_ok() {
+            num_sand += 1;
+            frames.record(|| self.image());
+        }
+        frames.push(|| self.image());
+        num_sand
+    }
+    fn build(paths: Vec<Path>, floor: bool) -> Cave {
+        let lowest_rock = coord_min_max(&paths, cmp::max, row);
+        let mut cave = Cave {
+            cells: OffsetGrid::new(CaveCell::Empty),
+            lowest_rock,
+            floor,
+            view: (START, START),
+        };
+        for path in paths {
+            cave.add_path(path);
+        }
+        let (mut top_left, mut bottom_right) = cave.cells.bounds().unwrap();
+        top_left.y = cmp::min(top_left.y, START.y);
+        if floor {
+            // the pile of sand spreads out one column per row
+            let floor_row = lowest_rock + 2;
+            top_left.x = cmp::min(top_left.x, START.x - floor_row);
+            bottom_right.x = cmp::max(bottom_right.x, START.x + floor_row);
+            bottom_right.y = floor_row;
+        }
+        cave.view = (top_left, bottom_right);
+        cave
+    }
+    fn drop_sand(&mut self, start: &Point) -> Result<(), &str> {
+        let mut sand_loc = *start;
+        if self.get_cell(start) == Some(CaveCell::Sand) {
+            return Err("Puzzle complete");
+        }
+        'falling: loop {
+            // straight down, then down & left, then down & right
+            for dir in [Direction::Down, Direction::DownLeft, Direction::DownRight] {
+                match self.get_cell(&(sand_loc + dir)) {
+                    Some(CaveCell::Empty) => {
+                        sand_loc += dir;
+                        continue 'falling;
+                    }
+                    Some(_) => (),
+                    // below all of the rock, into the abyss
+                    None => return Err("Puzzle complete"),
+                }
+            }
+            break;
+        }
+        self.cells.set(sand_loc, CaveCell::Sand);
+        Ok(())
+    }
+
+    fn add_path(&mut self, path: Path) {
+        let mut points = path.points.iter();
+        let mut current_point = points.next().expect("need more than zero points");
+        for next_point in points {
+            for point in points_between(current_point, next_point) {
+                self.cells.set(point, CaveCell::Rock);
+            }
+            current_point = next_point;
+        }
+    }
+
+    fn get_cell(&self, point: &Point) -> Option<CaveCell> {
+        match self.floor {
+            true if point.y == self.lowest_rock + 2 => Some(CaveCell::Rock),
+            false if point.y > self.lowest_rock => None,
+            _ => Some(*self.cells.get(point)),
+        }
+    }
+
+    // the cells in view, from the top down to the floor
+    fn snapshot(&self) -> Grid<CaveCell> {
+        let (top_left, bottom_right) = self.view;
+        let num_rows = (bottom_right.y - top_left.y + 1) as usize;
+        let num_cols = (bottom_right.x - top_left.x + 1) as usize;
+        let mut grid = Grid::new(num_rows, num_cols, CaveCell::Empty);
+        for (index, cell) in grid.data.iter_mut().enumerate() {
+            let point = Point::new(
+                top_left.x + (index % num_cols) as i32,
+                top_left.y + (index / num_cols) as i32,
+            );
+            *cell = self.get_cell(&point).unwrap_or(CaveCell::Empty);
+        }
+        grid
+    }
+
+    fn display(&self) {
+        eprint!(
+            "{}",
+            render::ansi(&self.snapshot(), |cell| (cell.symbol(), cell.color()))
+        );
+    }
+
+    fn image(&self) -> Image {
+        Image::from_grid(&self.snapshot(), CaveCell::color)
+    }
+}
+
+fn points_between(p1: &Point, p2: &Point) -> Vec<Point> {
+    if p1.x != p2.x && p1.y != p2.y {
+        eprintln!("{p1:?} {p2:?}");
+        panic!("rows and cols both different!");
+    }
+    // one unit step along the row or column, from p1 to p2
+    let step = (*p2 - *p1).signum();
+    let mut points = vec![*p1];
+    let mut point = *p1;
+    while point != *p2 {
+        point += step;
+        points.push(point);
+    }
+    points
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+enum CaveCell {
+    Empty,
+    Rock,
+    Sand,
+}
+
+impl CaveCell {
+    fn symbol(&self) -> char {
+        match self {
+            CaveCell::Empty => EMPTY,
+            CaveCell::Rock => ROCK,
+            CaveCell::Sand => SAND,
+        }
+    }
+
+    fn color(&self) -> Rgb {
+        match self {
+            CaveCell::Empty => render::BLACK,
+            CaveCell::Rock => render::ROCK,
+            CaveCell::Sand => render::SAND,
+        }
+    }
+}
+
+// x is the column, y the row (distance down from the top)
+type Point = Point2D<i32>;
+
+fn col(p: &Point) -> i32 {
+    p.x
+}
+
+fn row(p: &Point) -> i32 {
+    p.y
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Path {
+    points: Vec<Point>,
+}
+
+fn coord_min_max(
+    paths: &[Path],
+    cmp_fn: impl Fn(i32, i32) -> i32,
+    mut coordinate_fn: impl Fn(&Point) -> i32,
+) -> i32 {
+    // output the min or max row or column for any set of paths
+    let max: i32 = paths
+        .iter()
+        .map(|path| {
+            path.points
+                .iter()
+                .map(&mut coordinate_fn)
+                .reduce(|max, item| cmp_fn(max, item))
+                .expect("Path should have points")
+        })
+        .reduce(|max, item| cmp_fn(max, item))
+        .expect("Path vector should have paths.");
+    max
+}
+
+fn parse_point(point: &str) -> Point {
+    let values: Vec<i32> = point
+        .split(',')
+        .map(|p| p.parse::<i32>().expect("invalid input"))
+        .collect();
+    Point::new(values[0], values[1])
+}
+
+fn parse_path(path: &str) -> Path {
+    let points: Vec<Point> = path.split(" -> ").map(parse_point).collect();
+    Path { points }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_between() {
+        let p1 = Point::new(5, 5);
+        let p2 = Point::new(5, 6);
+        let pb = points_between(&p1, &p2);
+        assert_eq!(pb.len(), 2);
+        let p1 = Point::new(1, 5);
+        let p2 = Point::new(4, 5);
+        let pb = points_between(&p1, &p2);
+        assert_eq!(pb.len(), 4);
+        assert_eq!(pb[1].y, 5);
+        assert_eq!(pb[1].x, 2);
+        let p1 = Point::new(1, 5);
+        let p2 = Point::new(1, 1);
+        let pb = points_between(&p1, &p2);
+        assert_eq!(pb.len(), 5);
+        assert_eq!(pb[1].y, 4);
+        assert_eq!(pb[4].y, 1);
+    }
+
+    #[test]
+    fn test_max_min() {
+        let p = vec![
+            parse_path("498,4 -> 498,6 -> 496,6"),
+            parse_path("503,4 -> 502,4 -> 502,9 -> 494,9"),
+        ];
+        assert_eq!(coord_min_max(&p, cmp::max, col), 503);
+        assert_eq!(coord_min_max(&p, cmp::min, col), 494);
+        // assert_eq!(p.x_min(), 496);
+    }
+    #[test]
+    fn test_parse_path() {
+        let input = "498,4 -> 498,6 -> 496,6";
+        let path = parse_path(input);
+        assert_eq!(path.points[0].x, 498);
+        assert_eq!(path.points[2].y, 6);
+    }
+    #[test]
+    fn test_fill() {
+        let paths = vec![
+            parse_path("498,4 -> 498,6 -> 496,6"),
+            parse_path("503,4 -> 502,4 -> 502,9 -> 494,9"),
+        ];
+        assert_eq!(
+            Cave::build(paths.clone(), false).fill(&START, &mut Recorder::disabled()),
+            24
+        );
+        assert_eq!(
+            Cave::build(paths.clone(), true).fill(&START, &mut Recorder::disabled()),
+            93
+        );
+        // a pile much wider than the rock, that goes off to negative columns
+        let mut cave = Cave::build(paths.clone(), true);
+        assert_eq!(cave.fill(&Point::new(0, 0), &mut Recorder::disabled()), 121);
+        let (top_left, _) = cave.cells.bounds().unwrap();
+        assert_eq!(top_left.x, -10);
+
+        let mut cave = Cave::build(paths.clone(), false);
+        cave.fill(&START, &mut Recorder::disabled());
+        let snapshot = cave.snapshot();
+        // columns 494 to 503, and rows 0 to 9
+        assert_eq!((snapshot.num_rows, snapshot.num_cols), (10, 10));
+        assert_eq!(snapshot.get(0, 6).unwrap(), CaveCell::Empty);
+        assert_eq!(snapshot.get(2, 6).unwrap(), CaveCell::Sand);
+        assert_eq!(snapshot.get(9, 0).unwrap(), CaveCell::Rock);
+        assert_eq!(cave.image().width, 10);
+
+        let mut cave = Cave::build(paths, true);
+        let mut frames = Recorder::new();
+        assert_eq!(cave.fill(&START, &mut frames), 93);
+        // the empty cave, one frame per unit of sand, and the end again
+        assert_eq!(frames.frames().len(), 95);
+        let sizes: Vec<_> = frames
+            .frames()
+            .iter()
+            .map(|f| (f.width, f.height))
+            .collect();
+        assert!(sizes.iter().all(|size| *size == sizes[0]));
+    }
+
+    #[test]
+    fn test_parse_point() {
+        assert_eq!(parse_point("123,45"), Point::new(123, 45));
+    }
+}
@@ -1,17 +1,15 @@
+use crate::common::geom::Point2D;
+use crate::solver::{Answer, Solver};
 use nom::{bytes::complete::tag, bytes::complete::take_till, character::complete::i32, IResult};
 use std::cmp;
 use std::collections::HashSet;
 use std::ops::RangeInclusive;
-use std::time;
+use std::thread;
 
-#[derive(Debug, Eq, PartialEq, Hash)]
-struct Point {
-    x: i32,
-    y: i32,
-}
+pub type Point = Point2D<i32>;
 
 #[derive(Debug, PartialEq)]
-struct SensorCoverage {
+pub struct SensorCoverage {
     p: Point,
     size: i32,
 }
@@ -31,7 +29,7 @@ fn build_sensor_beacon_map(input: String) -> (Vec<SensorCoverage>, HashSet<Point
     let mut beacons: HashSet<Point> = HashSet::new();
     for line in input.lines() {
         let (sensor, beacon) = parse_input(line);
-        let size = manhattan_distance(&sensor, &beacon);
+        let size = sensor.manhattan(&beacon);
         let sensor_cov = SensorCoverage { p: sensor, size };
         sensors.push(sensor_cov);
         beacons.insert(beacon);
@@ -49,32 +47,52 @@ fn part_2(
     beacons: &HashSet<Point>,
 ) -> i64 {
     let ranges = split_range(limits.clone(), 10);
-    for range in ranges {
-        thread::spawn(move || {
-            let start_row = *range.start();
-            let end_row = *range.end();
-            println!("Checking from {} to {}", &start_row, &end_row);
-            for row in start_row..=end_row {
-                let nrnb = num_row_non_beacon(row, &sensors, &beacons, Some(&limits));
-                if nrnb == *limits.end() {
-                    let mut row_covered_ranges = get_row_coverage_ranges(row, &sensors);
-                    row_covered_ranges = truncate_ranges(row_covered_ranges, 0, *limits.end());
-                    row_covered_ranges = merge_ranges(row_covered_ranges);
-                    return tuning_frequency(row, row_covered_ranges);
-                }
-            }
-        });
-    }
-    unreachable!("Solution should exist");
+    thread::scope(|s| {
+        let searches: Vec<_> = ranges
+            .into_iter()
+            .map(|range| {
+                let limits = &limits;
+                s.spawn(move || {
+                    let start_row = *range.start();
+                    let end_row = *range.end();
+                    eprintln!("Checking from {} to {}", &start_row, &end_row);
+                    for row in start_row..=end_row {
+                        let nrnb = num_row_non_beacon(row, sensors, beacons, Some(limits));
+                        if nrnb == *limits.end() {
+                            let mut row_covered_ranges = get_row_coverage_ranges(row, sensors);
+                            row_covered_ranges =
+                                truncate_ranges(row_covered_ranges, 0, *limits.end());
+                            row_covered_ranges = merge_ranges(row_covered_ranges);
+                            return Some(tuning_frequency(row, row_covered_ranges));
+                        }
+                    }
+                    None
+                })
+            })
+            .collect();
+        searches
+            .into_iter()
+            .find_map(|search| search.join().expect("Search thread should not panic"))
+            .expect("Solution should exist")
+    })
 }
 
-pub fn main(contents: String) {
-    let (sensors, beacons) = build_sensor_beacon_map(contents);
-    println!("Part 1: {}", part_1(2_000_000, &sensors, &beacons));
-    let start_time = time::Instant::now();
-    println!("Part 2: {}", part_2(0..=4_000_000, &sensors, &beacons));
-    let end_time = start_time.elapsed();
-    println!("Part 2 completed in {} seconds.", end_time.as_secs_f32());
+pub struct Day15;
+
+impl Solver for Day15 {
+    type Input = (Vec<SensorCoverage>, HashSet<Point>);
+
+    fn parse(&self, contents: String) -> Self::Input {
+        build_sensor_beacon_map(contents)
+    }
+
+    fn part_1(&self, (sensors, beacons): &Self::Input) -> Answer {
+        part_1(2_000_000, sensors, beacons).into()
+    }
+
+    fn part_2(&self, (sensors, beacons): &Self::Input) -> Answer {
+        part_2(0..=4_000_000, sensors, beacons).into()
+    }
 }
 
 fn tuning_frequency(row: i32, covered_ranges: Vec<RangeInclusive<i32>>) -> i64 {
@@ -122,11 +140,7 @@ fn parse_input(input: &str) -> (Point, Point) {
     let (input, y1) = i32_after_eq(input).expect("expect valid input");
     let (input, x2) = i32_after_eq(input).expect("expect valid input");
     let (_, y2) = i32_after_eq(input).expect("expect valid input");
-    (Point { x: x1, y: y1 }, Point { x: x2, y: y2 })
-}
-
-fn manhattan_distance(p1: &Point, p2: &Point) -> i32 {
-    (p1.x - p2.x).abs() + (p1.y - p2.y).abs()
+    (Point::new(x1, y1), Point::new(x2, y2))
 }
 
 fn i32_after_eq(s: &str) -> IResult<&str, i32> {
@@ -222,7 +236,7 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        let input = include_str!("../inputs/2022.15.test").to_string();
+        let input = include_str!("../../inputs/2022.15.test").to_string();
 
         let (sensors, beacons) = build_sensor_beacon_map(input);
         assert_eq!(part_1(10, &sensors, &beacons), 26);
@@ -230,14 +244,14 @@ mod tests {
 
     #[test]
     fn test_part_2() {
-        let input = include_str!("../inputs/2022.15.test").to_string();
+        let input = include_str!("../../inputs/2022.15.test").to_string();
         let (sensors, beacons) = build_sensor_beacon_map(input);
         assert_eq!(part_2(0..=20, &sensors, &beacons), 56000011);
     }
 
     #[bench]
     fn bench_part_2(b: &mut Bencher) {
-        let input = include_str!("../inputs/2022.15.test").to_string();
+        let input = include_str!("../../inputs/2022.15.test").to_string();
         let (sensors, beacons) = build_sensor_beacon_map(input);
         b.iter(|| part_2(0..=20, &sensors, &beacons));
     }
@@ -303,7 +317,7 @@ mod tests {
     fn test_md() {
         let p1 = Point { x: 0, y: 5 };
         let p2 = Point { x: 1, y: 2 };
-        assert_eq!(manhattan_distance(&p1, &p2), 4);
+        assert_eq!(p1.manhattan(&p2), 4);
     }
     #[test]
     fn test_parse() {
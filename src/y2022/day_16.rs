@@ -1,3 +1,4 @@
+use crate::solver::{Answer, Solver};
 use nom::{
     bytes::complete::tag, bytes::complete::take_till, bytes::complete::take_while,
     multi::separated_list1, sequence::tuple, IResult,
@@ -52,7 +53,7 @@ impl NetworkState {
 }
 
 #[derive(Debug, Clone)]
-struct Valve {
+pub struct Valve {
     name: String,
     flow_rate: u32,
     // is_open: bool,
@@ -85,32 +86,39 @@ fn parse_input(input: &str) -> (&str, u32, Vec<&str>) {
     (name, flow_rate, tunnels)
 }
 
-/* NOTE: If not using full program from github.com/blairfrandeen/2022-AoC/
-simply comment out the function definition below, and uncomment the following
-two lines, replacing the '../inputs/2022.16' with the appropriate file. */
-pub fn main(contents: String) {
-    // let contents = include_str!("../test_input.txt").to_string();
-    let mut network: ValveNetwork = ValveNetwork::new();
-    for line in contents.lines() {
-        let new_valve: Valve = Valve::build(line);
-        network.insert(new_valve.name.clone(), new_valve);
+pub struct Day16;
+
+impl Solver for Day16 {
+    type Input = ValveNetwork;
+
+    fn parse(&self, contents: String) -> ValveNetwork {
+        let mut network: ValveNetwork = ValveNetwork::new();
+        for line in contents.lines() {
+            let new_valve: Valve = Valve::build(line);
+            network.insert(new_valve.name.clone(), new_valve);
+        }
+        network
+    }
+
+    fn part_1(&self, network: &ValveNetwork) -> Answer {
+        let current_position = "AA"; // starting position
+        let time_remaining = 30;
+        let initial_state = NetworkState {
+            time_remaining,
+            current_position: current_position.to_string(),
+            open_valves: vec![None; time_remaining as usize],
+        };
+        eprintln!(
+            "Max pressure release: {}",
+            initial_state.remaining_potential(network)
+        );
+        let mut visited = HashSet::<NetworkState>::new();
+        find_max_pressure(initial_state, network, &mut visited, 0).into()
+    }
+
+    fn part_2(&self, _network: &ValveNetwork) -> Answer {
+        Answer::Unsolved
     }
-    let current_position = "AA"; // starting position
-    let time_remaining = 30;
-    let initial_state = NetworkState {
-        time_remaining,
-        current_position: current_position.to_string(),
-        open_valves: vec![None; time_remaining as usize],
-    };
-    println!(
-        "Max pressure release: {}",
-        initial_state.remaining_potential(&network)
-    );
-    let mut visited = HashSet::<NetworkState>::new();
-    println!(
-        "max pressure attainable: {:?}",
-        find_max_pressure(initial_state, &network, &mut visited, 0)
-    );
 }
 
 // HashMap<NetworkState, u32>
@@ -152,7 +160,7 @@ fn find_max_pressure(
     // }
     /*
 
-    println!(
+    eprintln!(
         "Time Remaining: {}, current pressure: {}, current position: {} Open valves: {:?}",
         &network_state.time_remaining,
         &max_pressure,
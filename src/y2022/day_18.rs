@@ -1,22 +1,35 @@
+use crate::common::search;
+use crate::solver::{Answer, Solver};
 use std::cmp;
 use std::collections::HashSet;
 
-pub fn main(contents: String) {
-    let points: HashSet<Point3D> = contents.lines().map(Point3D::build).collect();
-    let obj = Object3D { points };
-    let part_1 = obj.count_sides();
-    println!("Part 1: {part_1}");
-    let part_2: u32 = part_1
-        - obj
-            .voids()
-            .into_iter()
-            .map(|v| v.count_sides())
-            .sum::<u32>();
-    println!("Part 2: {part_2}");
+pub struct Day18;
+
+impl Solver for Day18 {
+    type Input = Object3D;
+
+    fn parse(&self, contents: String) -> Object3D {
+        let points: HashSet<Point3D> = contents.lines().map(Point3D::build).collect();
+        Object3D { points }
+    }
+
+    fn part_1(&self, obj: &Object3D) -> Answer {
+        obj.count_sides().into()
+    }
+
+    fn part_2(&self, obj: &Object3D) -> Answer {
+        let part_2: u32 = obj.count_sides()
+            - obj
+                .voids()
+                .into_iter()
+                .map(|v| v.count_sides())
+                .sum::<u32>();
+        part_2.into()
+    }
 }
 
 #[derive(Debug)]
-struct Object3D {
+pub struct Object3D {
     points: HashSet<Point3D>,
 }
 
@@ -31,38 +44,22 @@ impl Object3D {
     }
 
     fn chunks(&self) -> Vec<Object3D> {
+        // groups of points that touch each other, found one at a time
         let mut chunks: Vec<Object3D> = Vec::new();
-        let mut all_pts: Vec<Point3D> = self.points.clone().into_iter().collect();
-        while !all_pts.is_empty() {
-            let mut to_visit: Vec<Point3D> = Vec::new();
-            let mut visited: Vec<Point3D> = Vec::new();
-            let current_point = all_pts.pop().expect("at least one point");
-            for neighbor in current_point.neighbors() {
-                if !visited.contains(&neighbor)
-                    && !to_visit.contains(&neighbor)
-                    && all_pts.contains(&neighbor)
-                {
-                    to_visit.push(neighbor);
-                }
-            }
-            visited.push(current_point);
-            while let Some(current_point) = to_visit.pop() {
-                for neighbor in current_point.neighbors() {
-                    if !visited.contains(&neighbor)
-                        && !to_visit.contains(&neighbor)
-                        && all_pts.contains(&neighbor)
-                    {
-                        to_visit.push(neighbor);
-                    }
-                }
-                visited.push(
-                    all_pts.remove(all_pts.iter().position(|p| p == &current_point).unwrap()),
-                );
-            }
-            let points: HashSet<Point3D> = visited.into_iter().collect();
+        let mut all_pts = self.points.clone();
+        while let Some(&start) = all_pts.iter().next() {
+            let search = search::bfs(
+                [start],
+                |p| {
+                    p.neighbors()
+                        .into_iter()
+                        .filter(|n| self.points.contains(n))
+                },
+                |_| false,
+            );
+            let points: HashSet<Point3D> = search.distances().keys().copied().collect();
+            all_pts.retain(|p| !points.contains(p));
             chunks.push(Object3D { points });
-            // println!("{:?}", chunks);
-            // println!();
         }
         chunks
     }
@@ -1,9 +1,21 @@
-pub fn main(contents: String) {
-    let part_1_score = part_1(&contents);
-    println!("Part 1: {part_1_score}");
+use crate::solver::{Answer, Solver};
 
-    let part_2_score = part_2(&contents);
-    println!("Part 2: {part_2_score}");
+pub struct Day2;
+
+impl Solver for Day2 {
+    type Input = String;
+
+    fn parse(&self, contents: String) -> String {
+        contents
+    }
+
+    fn part_1(&self, contents: &String) -> Answer {
+        part_1(contents).into()
+    }
+
+    fn part_2(&self, contents: &String) -> Answer {
+        part_2(contents).into()
+    }
 }
 
 fn part_1(contents: &str) -> i32 {
@@ -1,14 +1,27 @@
 // note: inputs are _not_ unique!
 // my file has 5000 lines, 3638 unique numbers
+use crate::solver::{Answer, Solver};
 use std::collections::VecDeque;
 
 type Signal = VecDeque<(usize, i64)>;
 const DECRYPTION_KEY: i64 = 811_589_153;
 
-pub fn main(contents: String) {
-    let input = parse_input(contents);
-    println!("Part 1: {}", part_1(input.clone()));
-    println!("Part 2: {}", part_2(input));
+pub struct Day20;
+
+impl Solver for Day20 {
+    type Input = Signal;
+
+    fn parse(&self, contents: String) -> Signal {
+        parse_input(contents)
+    }
+
+    fn part_1(&self, signal: &Signal) -> Answer {
+        part_1(signal.clone()).into()
+    }
+
+    fn part_2(&self, signal: &Signal) -> Answer {
+        part_2(signal.clone()).into()
+    }
 }
 
 fn part_2(mut signal: Signal) -> i64 {
@@ -110,7 +123,7 @@ mod tests {
         ];
         let indices = vec![0, 0, 1, 2, 2, 3, 5]; // manual input
         let mut test_input: Signal =
-            parse_input(include_str!("../inputs/2022.20.test").to_string());
+            parse_input(include_str!("../../inputs/2022.20.test").to_string());
         for i in 0..7 {
             mix_element(&mut test_input, indices[i]);
             assert_eq!(_sig_to_vec(&test_input), mix_sequence[i + 1]);
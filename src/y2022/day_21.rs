@@ -1,7 +1,27 @@
+use crate::solver::{Answer, Solver};
 use std::collections::HashMap;
 use std::collections::VecDeque;
 
-pub fn main(contents: String) {
+pub struct Day21;
+
+impl Solver for Day21 {
+    type Input = HashMap<String, i64>;
+
+    fn parse(&self, contents: String) -> HashMap<String, i64> {
+        resolve_monkeys(&contents)
+    }
+
+    fn part_1(&self, monkeys: &HashMap<String, i64>) -> Answer {
+        (*monkeys.get("root").expect("Root exists")).into()
+    }
+
+    fn part_2(&self, _monkeys: &HashMap<String, i64>) -> Answer {
+        Answer::Unsolved
+    }
+}
+
+fn resolve_monkeys(contents: &str) -> HashMap<String, i64> {
+    // keep working through the list until every monkey has a number
     let mut monkeys: HashMap<String, i64> = HashMap::new();
     let mut unknowns: VecDeque<&str> = VecDeque::new();
     for line in contents.lines() {
@@ -19,9 +39,8 @@ pub fn main(contents: String) {
             unknowns.push_front(next);
         }
     }
-    println!("Part 1: {}", monkeys.get("root").expect("Root exists"));
     // println!("{:?}", monkeys);
-    // println!("{:?}", unknowns);
+    monkeys
 }
 
 fn parse_unknown(input: &str, monkeys: &HashMap<String, i64>) -> Option<(String, i64)> {
@@ -1,10 +1,22 @@
+use crate::solver::{Answer, Solver};
 use std::collections::HashSet;
 
-pub fn main(contents: String) {
-    let part_1_total = part_1(&contents);
-    println!("Part 1: {part_1_total}");
-    let part_2_total = part_2(&contents);
-    println!("Part 2: {part_2_total}");
+pub struct Day3;
+
+impl Solver for Day3 {
+    type Input = String;
+
+    fn parse(&self, contents: String) -> String {
+        contents
+    }
+
+    fn part_1(&self, contents: &String) -> Answer {
+        part_1(contents).into()
+    }
+
+    fn part_2(&self, contents: &String) -> Answer {
+        part_2(contents).into()
+    }
 }
 
 fn part_1(contents: &str) -> u32 {
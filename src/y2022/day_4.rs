@@ -1,8 +1,21 @@
-pub fn main(contents: String) {
-    let part_1_ans = part_1(&contents);
-    println!("Part 1: {part_1_ans}");
-    let part_2_ans = part_2(&contents);
-    println!("Part 2: {part_2_ans}");
+use crate::solver::{Answer, Solver};
+
+pub struct Day4;
+
+impl Solver for Day4 {
+    type Input = String;
+
+    fn parse(&self, contents: String) -> String {
+        contents
+    }
+
+    fn part_1(&self, contents: &String) -> Answer {
+        part_1(contents).into()
+    }
+
+    fn part_2(&self, contents: &String) -> Answer {
+        part_2(contents).into()
+    }
 }
 
 fn part_1(contents: &str) -> i32 {
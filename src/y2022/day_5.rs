@@ -1,6 +1,8 @@
 // 9 stacks
 // ~500 instructions
 // No more than ~20 crates moved at a time
+use crate::solver::{Answer, Solver};
+
 #[derive(PartialEq, Debug)]
 struct Instruction {
     qty: usize,
@@ -8,20 +10,33 @@ struct Instruction {
     to: usize,
 }
 
-pub fn main(contents: String) {
-    let mut stack_cols_p1: Vec<Vec<char>> = initialize_stacks(&contents);
-    let mut stack_cols_p2 = stack_cols_p1.clone();
+pub struct Day5;
+
+impl Solver for Day5 {
+    type Input = String;
+
+    fn parse(&self, contents: String) -> String {
+        contents
+    }
+
+    fn part_1(&self, contents: &String) -> Answer {
+        rearrange(contents, false).into()
+    }
+
+    fn part_2(&self, contents: &String) -> Answer {
+        rearrange(contents, true).into()
+    }
+}
+
+fn rearrange(contents: &str, part_2: bool) -> String {
+    let mut stack_cols: Vec<Vec<char>> = initialize_stacks(contents);
     let mut instructions = contents.lines();
     instructions.find(|&b| b.is_empty());
     for line in instructions {
         // execute the moves as they come
-        stack_cols_p1 = execute_instruction(stack_cols_p1, parse_instruction(line), false);
-        stack_cols_p2 = execute_instruction(stack_cols_p2, parse_instruction(line), true);
+        stack_cols = execute_instruction(stack_cols, parse_instruction(line), part_2);
     }
-    let message_p1 = assemble_message(stack_cols_p1);
-    let message_p2 = assemble_message(stack_cols_p2);
-    println!("Part 1: {message_p1}");
-    println!("Part 2: {message_p2}");
+    assemble_message(stack_cols)
 }
 
 fn initialize_stacks(contents: &str) -> Vec<Vec<char>> {
@@ -39,7 +54,7 @@ fn initialize_stacks(contents: &str) -> Vec<Vec<char>> {
                 .unwrap()
                 .to_digit(10) // convert from char to digit
                 .unwrap();
-            println!("We have {num_stacks} stacks!");
+            eprintln!("We have {num_stacks} stacks!");
 
             // Now we need to build the stacks
             // start by making empty stacks
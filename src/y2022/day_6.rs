@@ -1,10 +1,24 @@
 // 4096 characters in input string
 
+use crate::solver::{Answer, Solver};
 use std::collections::HashSet;
 
-pub fn main(contents: String) {
-    println!("Part 1: {}", find_marker(&contents, 4));
-    println!("Part 2: {}", find_marker(&contents, 14));
+pub struct Day6;
+
+impl Solver for Day6 {
+    type Input = String;
+
+    fn parse(&self, contents: String) -> String {
+        contents
+    }
+
+    fn part_1(&self, signal: &String) -> Answer {
+        find_marker(signal, 4).into()
+    }
+
+    fn part_2(&self, signal: &String) -> Answer {
+        find_marker(signal, 14).into()
+    }
 }
 
 fn find_marker(signal: &str, marker_size: usize) -> usize {
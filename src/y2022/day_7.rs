@@ -0,0 +1,743 @@
+/*
+Ok this is a hard one, a lot of parsing to think about
+If line starts with '$', it's a command
+Only commands are cd and ls
+If it's a cd command, that changes the current directory we're in
+If it's a ls command, then we get a list of what's in the directory;
+Every line until the next command (or EOF) is something that's in the
+directory.
+If the line starts with 'dir' it's another directory
+If it starts with a number then it's a filesize plus a filename.
+
+Do I need to save the filenames? Or can I just add up the file sizes
+and assign those sizes to the directory? That would make the problem easier,
+however there's a risk that I'll need the file sizes in part 2,
+which means I'd regret not grabbing the information to begin with.
+
+This seems like a good use of both the nom crate and the Rc<T> structure.
+
+Later: it turned into a tree after all, with every entry kept in one Vec and
+referring to each other by index, which is a lot less painful than Rc<RefCell>.
+Sizing directories by matching path prefixes was wrong, since `a` matched `ab`.
+*/
+
+use crate::common::render;
+use crate::solver::{Answer, Solver};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::{env, fmt, fs};
+
+const FS_SIZE: u64 = 70_000_000;
+const REQUIRED_SPACE: u64 = 30_000_000;
+
+// set to anything to stop on a transcript that doesn't add up
+pub const STRICT_VAR: &str = "AOC_STRICT";
+// set to a depth (or `all`) to print the tree and a `du` summary
+pub const TREE_VAR: &str = "AOC_TREE";
+
+pub struct Day7;
+
+impl Solver for Day7 {
+    type Input = FileSystem;
+
+    fn parse(&self, contents: String) -> FileSystem {
+        let (fs, issues) = FileSystem::read_transcript(&contents);
+        if strict() && !issues.is_empty() {
+            panic!(
+                "the transcript doesn't add up:\n{}",
+                issues
+                    .iter()
+                    .map(|i| i.to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            );
+        }
+        // otherwise carry on with the sizes as best they can be worked out
+        for issue in &issues {
+            eprintln!("Warning: {issue}");
+        }
+        fs
+    }
+
+    fn part_1(&self, fs: &FileSystem) -> Answer {
+        report(fs);
+        let part_1: u64 = fs
+            .find(|entry, size| entry.is_dir() && size < 100_000)
+            .into_iter()
+            .map(|id| fs.size(id))
+            .sum();
+        part_1.into()
+    }
+
+    fn part_2(&self, fs: &FileSystem) -> Answer {
+        // nothing at all needs deleting if there's already enough space
+        let free = FS_SIZE.saturating_sub(fs.size(ROOT));
+        let to_delete = REQUIRED_SPACE.saturating_sub(free);
+        fs.find(|entry, size| entry.is_dir() && size >= to_delete)
+            .into_iter()
+            .map(|id| fs.size(id))
+            .min()
+            .unwrap()
+            .into()
+    }
+}
+
+fn strict() -> bool {
+    env::var_os(STRICT_VAR).is_some_and(|v| !v.is_empty())
+}
+
+// How far down `AOC_TREE` asks to go: None if it isn't set, Some(None) for
+// the whole tree
+fn tree_depth() -> Option<Option<usize>> {
+    let depth = env::var(TREE_VAR).ok().filter(|depth| !depth.is_empty())?;
+    Some(depth.parse().ok())
+}
+
+// Show the tree as it was understood, to check against the transcript, if
+// that's been asked for
+fn report(fs: &FileSystem) {
+    if let Some(depth) = tree_depth() {
+        eprint!("{}", fs.tree(depth));
+        eprint!("{}", fs.du_report(depth));
+    }
+    if let Some(dir) = render::render_dir() {
+        let tree_path = dir.join("2022.7.tree.txt");
+        let json_path = dir.join("2022.7.json");
+        let saved = fs::create_dir_all(&dir)
+            .and_then(|_| fs::write(&tree_path, fs.tree(None)))
+            .and_then(|_| fs::write(&json_path, fs.to_json().to_string()));
+        match saved {
+            Ok(()) => eprintln!("Saved {} and {}", tree_path.display(), json_path.display()),
+            Err(e) => eprintln!("Could not save the filesystem: {e}"),
+        }
+    }
+}
+
+pub const ROOT: usize = 0;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Kind {
+    File(u64),
+    Dir(Vec<usize>),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Entry {
+    pub name: String,
+    pub parent: Option<usize>,
+    pub kind: Kind,
+}
+
+impl Entry {
+    pub fn is_dir(&self) -> bool {
+        matches!(self.kind, Kind::Dir(_))
+    }
+
+    pub fn children(&self) -> &[usize] {
+        match &self.kind {
+            Kind::Dir(children) => children,
+            Kind::File(_) => &[],
+        }
+    }
+}
+
+// Things in a transcript that would give the wrong sizes if taken at face value
+#[derive(Debug, PartialEq, Clone)]
+pub enum Anomaly {
+    // `cd` into a directory no `ls` has shown
+    UnknownDir(String),
+    // `cd` into something that was listed as a file
+    NotADir(String),
+    // the same directory listed again, with the same contents
+    DuplicateLs(String),
+    // the same directory listed again, with something different in it
+    ChangedLs(String),
+    // `cd ..` while already at the root
+    AboveRoot,
+    // a name listed as a file in one place and a directory in another
+    WrongKind(String),
+    // a line that's neither a command nor something in a listing
+    Unreadable(String),
+}
+
+impl fmt::Display for Anomaly {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Anomaly::UnknownDir(path) => write!(f, "cd into {path}, which wasn't listed"),
+            Anomaly::NotADir(path) => write!(f, "cd into {path}, which is a file"),
+            Anomaly::DuplicateLs(path) => write!(f, "{path} was already listed"),
+            Anomaly::ChangedLs(path) => {
+                write!(f, "{path} was listed before with different contents")
+            }
+            Anomaly::AboveRoot => write!(f, "cd .. from /, which has no parent"),
+            Anomaly::WrongKind(path) => {
+                write!(f, "{path} was listed as both a file and a directory")
+            }
+            Anomaly::Unreadable(line) => write!(f, "can't make sense of `{line}`"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Issue {
+    // numbered from 1, like an editor
+    pub line: usize,
+    pub anomaly: Anomaly,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.anomaly)
+    }
+}
+
+// Every file & directory, which refer to each other by their index in
+// `entries`. The root is always index 0, and since a directory has to exist
+// before anything can go in it, every entry comes after its parent.
+#[derive(Debug, PartialEq, Clone)]
+pub struct FileSystem {
+    entries: Vec<Entry>,
+    // total size of everything in or under each entry
+    sizes: Vec<u64>,
+}
+
+impl Default for FileSystem {
+    fn default() -> Self {
+        FileSystem {
+            entries: vec![Entry {
+                name: "/".to_string(),
+                parent: None,
+                kind: Kind::Dir(Vec::new()),
+            }],
+            sizes: vec![0],
+        }
+    }
+}
+
+impl FileSystem {
+    // Anything odd in the transcript is ignored; see `read_transcript`
+    pub fn from_transcript(transcript: &str) -> FileSystem {
+        FileSystem::read_transcript(transcript).0
+    }
+
+    // The filesystem, plus everything in the transcript that doesn't add up.
+    // A directory that's cd'd into without being listed is made anyway,
+    // `cd ..` at the root and `cd` into a file both stay where they are, a
+    // file that's listed again keeps the size it was first listed with, and
+    // whichever of a file and a directory with the same name comes first is
+    // kept. Lines that can't be read are skipped.
+    pub fn read_transcript(transcript: &str) -> (FileSystem, Vec<Issue>) {
+        let mut fs = FileSystem::default();
+        let mut issues = Vec::new();
+        let mut cwd = ROOT;
+        // the lines from each directory's first `ls`, sorted
+        let mut listings: HashMap<usize, Vec<&str>> = HashMap::new();
+        // the `ls` being read: (its line, the directory, what's in it)
+        let mut listing: Option<(usize, usize, Vec<&str>)> = None;
+
+        for (n, line) in transcript.lines().enumerate() {
+            let words: Vec<&str> = line.split_whitespace().collect();
+            if words.first() == Some(&"$") {
+                issues.extend(
+                    listing
+                        .take()
+                        .and_then(|listing| compare_listing(&mut listings, listing, &fs)),
+                );
+            }
+            let anomaly = match words[..] {
+                ["$", "cd", "/"] => {
+                    cwd = ROOT;
+                    None
+                }
+                ["$", "cd", ".."] => match fs.entries[cwd].parent {
+                    Some(parent) => {
+                        cwd = parent;
+                        None
+                    }
+                    None => Some(Anomaly::AboveRoot),
+                },
+                ["$", "cd", dir] => {
+                    let known = fs.child(cwd, dir).is_some();
+                    match fs.add_dir(cwd, dir) {
+                        Some(id) => {
+                            cwd = id;
+                            (!known).then(|| Anomaly::UnknownDir(fs.path(cwd)))
+                        }
+                        // stay where we are, rather than put things in a file
+                        None => fs.child(cwd, dir).map(|id| Anomaly::NotADir(fs.path(id))),
+                    }
+                }
+                ["$", "ls"] => {
+                    listing = Some((n + 1, cwd, Vec::new()));
+                    None
+                }
+                ["$", ..] => Some(Anomaly::Unreadable(line.trim().to_string())),
+                ["dir", dir] => match fs.add_dir(cwd, dir) {
+                    Some(_) => None,
+                    None => fs.child(cwd, dir).map(|id| Anomaly::WrongKind(fs.path(id))),
+                },
+                [size, name] => match size.parse() {
+                    Ok(size) => match fs.add_file(cwd, name, size) {
+                        Some(_) => None,
+                        None => fs
+                            .child(cwd, name)
+                            .map(|id| Anomaly::WrongKind(fs.path(id))),
+                    },
+                    Err(_) => Some(Anomaly::Unreadable(line.trim().to_string())),
+                },
+                [] => None,
+                _ => Some(Anomaly::Unreadable(line.trim().to_string())),
+            };
+            if let Some((_, _, lines)) = &mut listing {
+                if !line.starts_with('$') {
+                    lines.push(line.trim());
+                }
+            }
+            if let Some(anomaly) = anomaly {
+                issues.push(Issue {
+                    line: n + 1,
+                    anomaly,
+                });
+            }
+        }
+        issues.extend(listing.and_then(|listing| compare_listing(&mut listings, listing, &fs)));
+        // a listing's only checked once it's over, so put it back in order
+        issues.sort_by_key(|issue| issue.line);
+        (fs, issues)
+    }
+
+    pub fn entry(&self, id: usize) -> &Entry {
+        &self.entries[id]
+    }
+
+    pub fn size(&self, id: usize) -> u64 {
+        self.sizes[id]
+    }
+
+    pub fn child(&self, dir: usize, name: &str) -> Option<usize> {
+        self.entries[dir]
+            .children()
+            .iter()
+            .copied()
+            .find(|id| self.entries[*id].name == name)
+    }
+
+    fn add(&mut self, parent: usize, name: &str, kind: Kind) -> usize {
+        let id = self.entries.len();
+        let size = match kind {
+            Kind::File(size) => size,
+            Kind::Dir(_) => 0,
+        };
+        self.entries.push(Entry {
+            name: name.to_string(),
+            parent: Some(parent),
+            kind,
+        });
+        self.sizes.push(size);
+        if let Kind::Dir(children) = &mut self.entries[parent].kind {
+            children.push(id);
+        }
+        // everything above it gets bigger too
+        let mut ancestor = Some(parent);
+        while let Some(dir) = ancestor {
+            self.sizes[dir] += size;
+            ancestor = self.entries[dir].parent;
+        }
+        id
+    }
+
+    // The directory `name` in `parent`, which is made if it isn't there yet.
+    // None if `parent` isn't a directory, or `name` in it is a file.
+    pub fn add_dir(&mut self, parent: usize, name: &str) -> Option<usize> {
+        if !self.entries[parent].is_dir() {
+            return None;
+        }
+        match self.child(parent, name) {
+            Some(id) => self.entries[id].is_dir().then_some(id),
+            None => Some(self.add(parent, name, Kind::Dir(Vec::new()))),
+        }
+    }
+
+    // A file that's listed again is only counted once. None if `parent`
+    // isn't a directory, or `name` in it is a directory.
+    pub fn add_file(&mut self, parent: usize, name: &str, size: u64) -> Option<usize> {
+        if !self.entries[parent].is_dir() {
+            return None;
+        }
+        match self.child(parent, name) {
+            Some(id) => (!self.entries[id].is_dir()).then_some(id),
+            None => Some(self.add(parent, name, Kind::File(size))),
+        }
+    }
+
+    // e.g. "/", "/a", "/a/e"
+    pub fn path(&self, id: usize) -> String {
+        let mut names = Vec::new();
+        let mut current = id;
+        while let Some(parent) = self.entries[current].parent {
+            names.push(self.entries[current].name.as_str());
+            current = parent;
+        }
+        names.reverse();
+        format!("/{}", names.join("/"))
+    }
+
+    pub fn lookup(&self, path: &str) -> Option<usize> {
+        path.split('/')
+            .filter(|name| !name.is_empty())
+            .try_fold(ROOT, |dir, name| self.child(dir, name))
+    }
+
+    // every entry, depth first, with directories before what's in them
+    pub fn walk(&self, from: usize) -> Vec<usize> {
+        let mut order = Vec::new();
+        let mut to_visit = vec![from];
+        while let Some(id) = to_visit.pop() {
+            order.push(id);
+            to_visit.extend(self.entries[id].children().iter().rev());
+        }
+        order
+    }
+
+    // entries where `keep(entry, total size)` is true, depth first
+    pub fn find(&self, keep: impl Fn(&Entry, u64) -> bool) -> Vec<usize> {
+        self.walk(ROOT)
+            .into_iter()
+            .filter(|id| keep(&self.entries[*id], self.sizes[*id]))
+            .collect()
+    }
+
+    // (path, total size) of every directory, like `du`
+    pub fn du(&self) -> Vec<(String, u64)> {
+        self.find(|entry, _| entry.is_dir())
+            .into_iter()
+            .map(|id| (self.path(id), self.sizes[id]))
+            .collect()
+    }
+
+    // how many directories down from the root
+    pub fn depth(&self, id: usize) -> usize {
+        let mut depth = 0;
+        let mut current = id;
+        while let Some(parent) = self.entries[current].parent {
+            depth += 1;
+            current = parent;
+        }
+        depth
+    }
+
+    // Like `du -h --max-depth`: the size of each directory, down to
+    // `max_depth` below the root, with what's in a directory before it
+    pub fn du_report(&self, max_depth: Option<usize>) -> String {
+        let mut dirs = self.find(|entry, _| entry.is_dir());
+        dirs.retain(|id| max_depth.is_none_or(|max| self.depth(*id) <= max));
+        dirs.reverse();
+        dirs.iter()
+            .map(|id| format!("{}\t{}\n", human_size(self.sizes[*id]), self.path(*id)))
+            .collect()
+    }
+
+    // Like `tree`, with each entry's size
+    pub fn tree(&self, max_depth: Option<usize>) -> String {
+        let mut text = format!("/ ({})\n", human_size(self.sizes[ROOT]));
+        self.tree_lines(ROOT, "", max_depth, &mut text);
+        text
+    }
+
+    fn tree_lines(&self, dir: usize, indent: &str, max_depth: Option<usize>, text: &mut String) {
+        if max_depth.is_some_and(|max| self.depth(dir) >= max) {
+            return;
+        }
+        let children = self.entries[dir].children();
+        for (n, id) in children.iter().enumerate() {
+            let last = n == children.len() - 1;
+            let entry = &self.entries[*id];
+            let branch = if last { "└── " } else { "├── " };
+            let kind = if entry.is_dir() { "/" } else { "" };
+            let size = human_size(self.sizes[*id]);
+            text.push_str(&format!("{indent}{branch}{}{kind} ({size})\n", entry.name));
+            let indent = format!("{indent}{}", if last { "    " } else { "│   " });
+            self.tree_lines(*id, &indent, max_depth, text);
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        self.entry_json(ROOT)
+    }
+
+    fn entry_json(&self, id: usize) -> Value {
+        let entry = &self.entries[id];
+        match &entry.kind {
+            Kind::File(size) => json!({"name": entry.name, "type": "file", "size": size}),
+            Kind::Dir(children) => json!({
+                "name": entry.name,
+                "type": "dir",
+                "size": self.sizes[id],
+                "children": children.iter().map(|c| self.entry_json(*c)).collect::<Vec<Value>>(),
+            }),
+        }
+    }
+}
+
+// Remember a directory's first listing, and compare any later one with it
+fn compare_listing<'a>(
+    listings: &mut HashMap<usize, Vec<&'a str>>,
+    (line, dir, mut lines): (usize, usize, Vec<&'a str>),
+    fs: &FileSystem,
+) -> Option<Issue> {
+    lines.sort();
+    let anomaly = match listings.get(&dir) {
+        None => {
+            listings.insert(dir, lines);
+            return None;
+        }
+        Some(before) if *before == lines => Anomaly::DuplicateLs(fs.path(dir)),
+        Some(_) => Anomaly::ChangedLs(fs.path(dir)),
+    };
+    Some(Issue { line, anomaly })
+}
+
+// Sizes the way `du -h` shows them: 584, 9.1K, 93K, 46M
+pub fn human_size(size: u64) -> String {
+    let mut scaled = size as f64;
+    for unit in ["", "K", "M", "G", "T"] {
+        if scaled < 1024.0 || unit == "T" {
+            return match (unit, scaled < 10.0) {
+                ("", _) => size.to_string(),
+                (_, true) => format!("{:.1}{unit}", (scaled * 10.0).ceil() / 10.0),
+                (_, false) => format!("{}{unit}", scaled.ceil()),
+            };
+        }
+        scaled /= 1024.0;
+    }
+    unreachable!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    fn example() -> FileSystem {
+        FileSystem::from_transcript(&fs::read_to_string("inputs/2022.7.test").unwrap())
+    }
+
+    #[test]
+    fn test_tree() {
+        let fs = example();
+        let a = fs.lookup("/a").unwrap();
+        assert_eq!(fs.size(a), 94853);
+        assert_eq!(fs.size(fs.lookup("/a/e").unwrap()), 584);
+        assert_eq!(fs.size(ROOT), 48381165);
+        assert_eq!(
+            fs.entry(fs.lookup("/a/h.lst").unwrap()).kind,
+            Kind::File(62596)
+        );
+        assert_eq!(fs.path(fs.lookup("a/e/i").unwrap()), "/a/e/i");
+        assert_eq!(fs.path(ROOT), "/");
+        assert_eq!(fs.lookup("/x"), None);
+        assert_eq!(fs.entry(a).children().len(), 4);
+    }
+
+    #[test]
+    fn test_queries() {
+        let fs = example();
+        assert_eq!(
+            fs.du(),
+            vec![
+                ("/".to_string(), 48381165),
+                ("/a".to_string(), 94853),
+                ("/a/e".to_string(), 584),
+                ("/d".to_string(), 24933642),
+            ]
+        );
+        let small: Vec<String> = fs
+            .find(|entry, size| entry.is_dir() && size < 100_000)
+            .into_iter()
+            .map(|id| fs.path(id))
+            .collect();
+        assert_eq!(small, vec!["/a", "/a/e"]);
+        assert_eq!(Day7.part_1(&fs), Answer::Number(95437));
+        assert_eq!(Day7.part_2(&fs), Answer::Number(24933642));
+    }
+
+    #[test]
+    fn test_reports() {
+        let fs = example();
+        assert_eq!(
+            fs.tree(None),
+            indoc! {"
+                / (47M)
+                ├── a/ (93K)
+                │   ├── e/ (584)
+                │   │   └── i (584)
+                │   ├── f (29K)
+                │   ├── g (2.5K)
+                │   └── h.lst (62K)
+                ├── b.txt (15M)
+                ├── c.dat (8.2M)
+                └── d/ (24M)
+                    ├── j (3.9M)
+                    ├── d.log (7.7M)
+                    ├── d.ext (5.4M)
+                    └── k (6.9M)
+            "}
+        );
+        assert_eq!(fs.tree(Some(1)).lines().count(), 5);
+        assert_eq!(fs.du_report(Some(1)), "24M\t/d\n93K\t/a\n47M\t/\n");
+        assert_eq!(fs.du_report(None).lines().next(), Some("24M\t/d"));
+        assert_eq!(fs.du_report(Some(0)), "47M\t/\n");
+
+        let json = fs.to_json();
+        assert_eq!(json["size"], 48381165);
+        assert_eq!(json["children"][0]["name"], "a");
+        assert_eq!(
+            json["children"][0]["children"][0]["children"][0]["size"],
+            584
+        );
+        assert_eq!(json["children"][1]["type"], "file");
+        assert!(json["children"][1].get("children").is_none());
+    }
+
+    #[test]
+    fn test_human_size() {
+        assert_eq!(human_size(0), "0");
+        assert_eq!(human_size(1023), "1023");
+        assert_eq!(human_size(1024), "1.0K");
+        assert_eq!(human_size(1025), "1.1K");
+        assert_eq!(human_size(10 * 1024), "10K");
+        assert_eq!(human_size(70_000_000), "67M");
+        assert_eq!(human_size(3 << 40), "3.0T");
+    }
+
+    #[test]
+    fn test_validate() {
+        let transcript = fs::read_to_string("inputs/2022.7.test").unwrap();
+        assert_eq!(FileSystem::read_transcript(&transcript).1, vec![]);
+
+        let transcript = indoc! {"
+            $ cd /
+            $ cd ..
+            $ ls
+            dir a
+            10 x
+            $ cd b
+            $ ls
+            5 y
+            $ cd /
+            $ ls
+            10 x
+            dir a
+            $ cd a
+            $ cd ..
+            $ ls
+            dir a
+            11 x
+        "};
+        let (fs, issues) = FileSystem::read_transcript(transcript);
+        let expected = [
+            (2, Anomaly::AboveRoot),
+            (6, Anomaly::UnknownDir("/b".to_string())),
+            (10, Anomaly::DuplicateLs("/".to_string())),
+            (15, Anomaly::ChangedLs("/".to_string())),
+        ];
+        assert_eq!(
+            issues,
+            expected
+                .map(|(line, anomaly)| Issue { line, anomaly })
+                .to_vec()
+        );
+        assert_eq!(
+            issues[1].to_string(),
+            "line 6: cd into /b, which wasn't listed"
+        );
+        // x keeps the size it was first listed with
+        assert_eq!(fs.size(ROOT), 15);
+
+        // everything after the cd into `a` is still in /, which makes it look
+        // like / was listed again
+        let (fs, issues) = FileSystem::read_transcript("$ ls\n10 a\n$ cd a\n$ ls\n5 b");
+        let expected = [
+            (3, Anomaly::NotADir("/a".to_string())),
+            (4, Anomaly::ChangedLs("/".to_string())),
+        ];
+        assert_eq!(
+            issues,
+            expected
+                .map(|(line, anomaly)| Issue { line, anomaly })
+                .to_vec()
+        );
+        assert_eq!(fs.entry(fs.lookup("/a").unwrap()).kind, Kind::File(10));
+        assert_eq!(fs.size(fs.lookup("/a").unwrap()), 10);
+        assert_eq!(fs.path(fs.lookup("/b").unwrap()), "/b");
+    }
+
+    #[test]
+    fn test_bad_lines() {
+        let transcript = indoc! {"
+            $ ls
+            dir a
+            10 b
+            foo bar
+            $ cd
+            $ cd a
+            $ ls
+            5 c
+            $ cd ..
+            $ ls
+            dir a
+            10 b
+            20 a
+            dir b
+        "};
+        let (fs, issues) = FileSystem::read_transcript(transcript);
+        let expected = [
+            (4, Anomaly::Unreadable("foo bar".to_string())),
+            (5, Anomaly::Unreadable("$ cd".to_string())),
+            (10, Anomaly::ChangedLs("/".to_string())),
+            (13, Anomaly::WrongKind("/a".to_string())),
+            (14, Anomaly::WrongKind("/b".to_string())),
+        ];
+        assert_eq!(
+            issues,
+            expected
+                .map(|(line, anomaly)| Issue { line, anomaly })
+                .to_vec()
+        );
+        assert_eq!(
+            issues[3].to_string(),
+            "line 13: /a was listed as both a file and a directory"
+        );
+        // the first of each is kept
+        assert!(fs.entry(fs.lookup("/a").unwrap()).is_dir());
+        assert_eq!(fs.entry(fs.lookup("/b").unwrap()).kind, Kind::File(10));
+        assert_eq!(fs.size(ROOT), 15);
+    }
+
+    #[test]
+    fn test_similar_names() {
+        // `a` used to be counted as containing everything in `ab`
+        let fs = FileSystem::from_transcript(
+            "$ cd /\n$ ls\ndir a\ndir ab\n$ cd a\n$ ls\n10 x\n$ cd ..\n$ cd ab\n$ ls\n20 x\n20 x",
+        );
+        assert_eq!(fs.size(fs.lookup("/a").unwrap()), 10);
+        assert_eq!(fs.size(fs.lookup("/ab").unwrap()), 20);
+        assert_eq!(fs.size(ROOT), 30);
+        // far less than 40M used, so nothing needs deleting
+        assert_eq!(Day7.part_2(&fs), Answer::from(10_u64));
+    }
+
+    #[test]
+    fn test_wrong_kind() {
+        let mut fs = FileSystem::default();
+        let a = fs.add_file(ROOT, "a", 10).unwrap();
+        let b = fs.add_dir(ROOT, "b").unwrap();
+        assert_eq!(fs.add_dir(ROOT, "a"), None);
+        assert_eq!(fs.add_file(ROOT, "b", 5), None);
+        assert_eq!(fs.add_file(a, "c", 5), None);
+        assert_eq!(fs.add_dir(ROOT, "b"), Some(b));
+        assert_eq!(fs.add_file(ROOT, "a", 10), Some(a));
+        assert_eq!(fs.size(ROOT), 10);
+        assert_eq!(fs.entry(a).kind, Kind::File(10));
+    }
+}
@@ -0,0 +1,79 @@
+use crate::common::{Direction, Grid};
+use crate::solver::{Answer, Solver};
+
+pub struct Day8;
+
+impl Solver for Day8 {
+    type Input = Grid;
+
+    fn parse(&self, contents: String) -> Grid {
+        Grid::build(contents).expect("trees should form a grid")
+    }
+
+    fn part_1(&self, grid: &Grid) -> Answer {
+        let num_visible: u32 = (0..grid.data.len())
+            .map(|x| is_visible(grid, x) as u32)
+            .sum();
+        num_visible.into()
+    }
+
+    fn part_2(&self, grid: &Grid) -> Answer {
+        let max_scenic_score: u32 = (0..grid.data.len())
+            .map(|x| scenic_score(grid, x))
+            .reduce(|max, item| if max >= item { max } else { item })
+            .unwrap();
+        max_scenic_score.into()
+    }
+}
+
+fn scenic_score(grid: &Grid, index: usize) -> u32 {
+    // how many trees can be seen in each direction, up to the first one as tall as this one
+    let height: u8 = grid.data[index];
+    Direction::LATERAL
+        .iter()
+        .map(|dir| {
+            grid.ray(index, *dir)
+                .unwrap()
+                .take_until_blocked(|h| *h >= height)
+                .count() as u32
+        })
+        .product()
+}
+
+fn is_visible(grid: &Grid, index: usize) -> bool {
+    // visible if every tree in some direction is shorter
+    let height: u8 = grid.data[index];
+    Direction::LATERAL.iter().any(|dir| {
+        grid.ray(index, *dir)
+            .unwrap()
+            .all(|i| grid.data[i] < height)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::tests;
+    use std::fs;
+
+    #[test]
+    fn test_visible() {
+        for n in 0..10 {
+            assert!(is_visible(&tests::mock_grid(), n));
+        }
+        let test_contents = fs::read_to_string("inputs/2022.8.test").unwrap();
+        let test_grid = Grid::build(test_contents).unwrap();
+        assert!(is_visible(&test_grid, 6));
+        assert!(!is_visible(&test_grid, 12));
+        assert!(!is_visible(&test_grid, 16));
+        assert!(is_visible(&test_grid, 17));
+        assert!(!is_visible(&test_grid, 18));
+    }
+    #[test]
+    fn test_scenic() {
+        let test_contents = fs::read_to_string("inputs/2022.8.test").unwrap();
+        let test_grid = Grid::build(test_contents).unwrap();
+        assert_eq!(scenic_score(&test_grid, 7), 4);
+        assert_eq!(scenic_score(&test_grid, 17), 8);
+    }
+}
@@ -0,0 +1,189 @@
+#![allow(unused)]
+use crate::common::animate::{self, Recorder};
+use crate::common::geom::{Direction, Point2D};
+use crate::common::render::{self, Image};
+use crate::solver::{Answer, Solver};
+use std::collections::HashSet;
+
+type Position = Point2D<i32>;
+
+fn follow(knot: &mut Position, other: &Position) {
+    // a knot that's been left behind moves one step, straight or
+    // diagonally, towards the knot ahead of it
+    if !touching(knot, other) {
+        *knot += (*other - *knot).signum();
+    }
+}
+
+fn touching(p1: &Position, p2: &Position) -> bool {
+    p1.chebyshev(p2) <= 1
+}
+
+fn parse_instruction(input: &str) -> (Direction, u32) {
+    let inst: Vec<&str> = input.trim().split(' ').collect();
+    let dir: Direction = inst[0].parse().unwrap();
+    let num: u32 = inst[1].parse().unwrap();
+    (dir, num)
+}
+
+struct Rope {
+    positions: Vec<Position>,
+    length: usize,
+}
+
+impl Rope {
+    fn build(length: usize) -> Rope {
+        if length < 2 {
+            panic!("Your rope is too short lol");
+        }
+        let mut positions = Vec::<Position>::new();
+        for index in 0..length {
+            let mut new_pos = Position::default();
+            positions.push(new_pos);
+        }
+        Rope { positions, length }
+    }
+}
+
+pub struct Day9;
+
+impl Solver for Day9 {
+    type Input = Vec<(Direction, u32)>;
+
+    fn parse(&self, contents: String) -> Vec<(Direction, u32)> {
+        contents.lines().map(parse_instruction).collect()
+    }
+
+    fn part_1(&self, instructions: &Vec<(Direction, u32)>) -> Answer {
+        let mut frames = Recorder::from_env();
+        let visits = tail_visits(instructions, 2, &mut frames);
+        animate::save("2022.9.part1", &rope_images(frames.frames()), 8);
+        visits.into()
+    }
+
+    fn part_2(&self, instructions: &Vec<(Direction, u32)>) -> Answer {
+        let mut frames = Recorder::from_env();
+        let visits = tail_visits(instructions, 10, &mut frames);
+        animate::save("2022.9.part2", &rope_images(frames.frames()), 8);
+        visits.into()
+    }
+}
+
+fn tail_visits(
+    instructions: &[(Direction, u32)],
+    length: usize,
+    frames: &mut Recorder<Vec<Position>>,
+) -> usize {
+    // number of unique positions visited by the last knot of the rope
+    let mut rope = Rope::build(length);
+    frames.record(|| rope.positions.clone());
+    let mut visited: HashSet<Position> = HashSet::new();
+    for (dir, num) in instructions {
+        for _ in 0..*num {
+            rope.positions[0] += *dir;
+            for p in 1..rope.length {
+                let next_knot = rope.positions[p - 1];
+                follow(&mut rope.positions[p], &next_knot);
+            }
+            visited.insert(rope.positions[rope.length - 1]);
+            frames.record(|| rope.positions.clone());
+        }
+    }
+    visited.len()
+}
+
+// Every frame drawn over the area the whole rope moves around in, with the
+// head in red, the tail in green, and the knots in between in white
+fn rope_images(frames: &[Vec<Position>]) -> Vec<Image> {
+    let all_knots = frames.iter().flatten();
+    let (Some(left), Some(right)) = (
+        all_knots.clone().map(|p| p.x).min(),
+        all_knots.clone().map(|p| p.x).max(),
+    ) else {
+        return Vec::new();
+    };
+    let top = all_knots.clone().map(|p| p.y).min().unwrap();
+    let bottom = all_knots.map(|p| p.y).max().unwrap();
+    let (width, height) = ((right - left + 1) as usize, (bottom - top + 1) as usize);
+    frames
+        .iter()
+        .map(|knots| {
+            let mut image = Image::new(width, height, render::BLACK);
+            let mut draw = |knot: &Position, color| {
+                image.set((knot.x - left) as usize, (knot.y - top) as usize, color)
+            };
+            // the ends go on top of any knots bunched up with them
+            knots.iter().for_each(|knot| draw(knot, render::WHITE));
+            draw(knots.last().unwrap(), render::GREEN);
+            draw(&knots[0], render::RED);
+            image
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let input = "R 4\n";
+        assert_eq!(parse_instruction(input), (Direction::Right, 4));
+        assert_eq!(parse_instruction("L 5\n"), (Direction::Left, 5));
+        assert_eq!(parse_instruction("L 15\n"), (Direction::Left, 15));
+    }
+    #[test]
+    fn test_follow() {
+        let mut p1 = Position::new(0, 0);
+        let p3 = Position::new(0, 2);
+        follow(&mut p1, &p3);
+        assert_eq!(p1, Position::new(0, 1));
+        follow(&mut p1, &p3); // should not move
+        assert_eq!(p1, Position::new(0, 1));
+        let p2 = Position::new(1, 3);
+        follow(&mut p1, &p2);
+        assert_eq!(p1, Position::new(1, 2));
+    }
+    #[test]
+    fn test_touch() {
+        let p1 = Position::new(0, 0);
+        let p2 = Position::new(1, 1);
+        let p3 = Position::new(0, 2);
+        let p4 = Position::new(0, 0);
+        let p5 = Position::new(1, 0);
+        assert!(touching(&p1, &p4));
+        assert!(touching(&p1, &p2));
+        assert!(!touching(&p1, &p3));
+        assert!(touching(&p1, &p5));
+        assert!(!touching(&p5, &p3));
+    }
+    #[test]
+    fn test_lead() {
+        let mut p = Position::new(0, 0);
+        p += Direction::Up;
+        assert_eq!(p.x, 0);
+        assert_eq!(p.y, -1);
+        p += Direction::Right;
+        assert_eq!(p.x, 1);
+        assert_eq!(p.y, -1);
+    }
+
+    #[test]
+    fn test_tail_visits() {
+        let instructions: Vec<(Direction, u32)> =
+            ["R 4", "U 4", "L 3", "D 1", "R 4", "D 1", "L 5", "R 2"]
+                .into_iter()
+                .map(parse_instruction)
+                .collect();
+        assert_eq!(tail_visits(&instructions, 2, &mut Recorder::disabled()), 13);
+        let mut frames = Recorder::new();
+        assert_eq!(tail_visits(&instructions, 10, &mut frames), 1);
+        // the starting position, and one for each of the 24 steps
+        assert_eq!(frames.frames().len(), 25);
+        let images = rope_images(frames.frames());
+        assert_eq!((images[0].width, images[0].height), (6, 5));
+        assert_eq!(images[0].get(0, 4), Some(render::RED));
+        assert_eq!(images[4].get(4, 4), Some(render::RED));
+        assert_eq!(images[4].get(0, 4), Some(render::GREEN));
+    }
+}